@@ -0,0 +1,13 @@
+//! Shows the Jacobson edge settling down as the iteration count grows
+//!
+//! cargo run --example convergence -- [SEED]
+
+use hcf::strategy::Jacobson;
+use hcf::simulation::simulate_strategy_seeded;
+
+fn main() {
+    let seed = std::env::args().nth(1).map_or(0, |seed| seed.parse().expect("the seed should be a number"));
+    for iterations in [10, 100, 1000, 10000] {
+        println!("{:>6} deals: {:.4}", iterations, simulate_strategy_seeded(&Jacobson, iterations, seed));
+    }
+}
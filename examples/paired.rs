@@ -0,0 +1,13 @@
+//! Runs every strategy on the same seeded deals so the edges differ only by strategy
+//!
+//! cargo run --example paired -- [SEED]
+
+use hcf::strategy::named_strategies;
+use hcf::simulation::simulate_strategy_seeded;
+
+fn main() {
+    let seed = std::env::args().nth(1).map_or(0, |seed| seed.parse().expect("the seed should be a number"));
+    for (name, strategy) in named_strategies() {
+        println!("{}: {:.4}", name, simulate_strategy_seeded(strategy, 2000, seed));
+    }
+}
@@ -0,0 +1,31 @@
+//! Walks through one seeded deal seat by seat as a Jacobson colluder sees it
+//!
+//! cargo run --example trace_deal -- [SEED]
+
+use hcf::card::{RANKS, card_name, vec_to_strings};
+use hcf::deck::Round;
+use hcf::rules::GameConfig;
+use hcf::evaluator::{calculate_average_result, get_best_flush};
+use hcf::strategy::{Jacobson, RoundContext, Strategy, try_get_strategy};
+
+fn main() {
+    let seed = std::env::args().nth(1).map_or(0, |seed| seed.parse().expect("the seed should be a number"));
+    let round = Round::seeded(seed);
+    let (hands, dealer_cards) = (round.player_hands(), round.dealer_cards());
+    let context = RoundContext::new(hands, dealer_cards, &GameConfig::STANDARD);
+
+    let mut signals = context.table_suits.map(|x| RANKS - x);
+    signals.sort();
+    println!("{}", round);
+    let code = try_get_strategy(signals).expect("a full table's signals always map to a strategy");
+    println!("Dealer suit signals {:?} give strategy code {}", signals, code);
+    for (seat, hand) in hands.iter().enumerate() {
+        let flush = get_best_flush(hand);
+        let decision = if Jacobson.plays(hand, &context) { "play" } else { "fold" };
+        println!(
+            "Seat {}: {:?} best flush {:?} -> {}, average result {:.4}",
+            seat, hand.map(card_name), vec_to_strings(&flush), decision,
+            calculate_average_result(hand, dealer_cards)
+        );
+    }
+}
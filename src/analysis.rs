@@ -0,0 +1,373 @@
+//! Distributions, variance reduction and significance tests
+
+use std::collections::BTreeMap;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::{SliceRandom, index};
+use rand_chacha::ChaCha8Rng;
+use rand::{Rng, SeedableRng};
+use crate::card::{DEALER_CARDS, DECK_SIZE, HAND_SIZE, NUM_PLAYERS, RANKS, Rank, Suit, card_name, repeated_card, vec_to_strings};
+use crate::deck::Deck;
+use crate::rules::GameConfig;
+use crate::evaluator::{calculate_average_result, compare_hands, get_best_flush, get_best_flush_masked, hand_mask, tally_average_result};
+use crate::probability::n_choose_k;
+use crate::strategy::{RoundContext, Strategy};
+use crate::simulation::{DealerDraw, SimulationResult, play_round};
+
+/// Whole-ante results taken one at a time. Keeps a count per distinct result plus a running
+/// (Welford) mean and variance, so memory stays the size of the result set however many hands are added
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResultAccumulator {
+    counts: BTreeMap<i32, u64>,
+    n: u64,
+    mean: f64,
+    // Sum of squared differences from the running mean
+    m2: f64,
+}
+impl ResultAccumulator {
+    pub fn new() -> Self {
+        ResultAccumulator::default()
+    }
+    pub fn push(&mut self, result: i32) {
+        *self.counts.entry(result).or_insert(0) += 1;
+        self.n += 1;
+        let delta = result as f64 - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (result as f64 - self.mean);
+    }
+    pub fn count(&self) -> u64 {
+        self.n
+    }
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+    /// Sample variance, 0.0 until there are two results
+    pub fn variance(&self) -> f64 {
+        if self.n > 1 { self.m2 / (self.n - 1) as f64 } else { 0.0 }
+    }
+    pub fn min(&self) -> Option<i32> {
+        self.counts.keys().next().copied()
+    }
+    pub fn max(&self) -> Option<i32> {
+        self.counts.keys().next_back().copied()
+    }
+    /// How many times each result was seen
+    pub fn counts(&self) -> &BTreeMap<i32, u64> {
+        &self.counts
+    }
+    /// Nearest-rank percentiles, see result_percentiles()
+    pub fn percentiles(&self, ps: &[f64]) -> Vec<i32> {
+        result_percentiles(&self.counts, ps)
+    }
+}
+
+/// Counts of each per-hand net result over seeded deals, settling against real dealer hands as
+/// simulate_direct() does so every result is a whole number of antes (a fold is -1)
+pub fn result_distribution(strategy: &dyn Strategy, iterations: usize, seed: u64) -> BTreeMap<i32, u64> {
+    result_accumulator(strategy, iterations, seed).counts
+}
+
+/// result_distribution() with the running mean and variance alongside the counts
+pub fn result_accumulator(strategy: &dyn Strategy, iterations: usize, seed: u64) -> ResultAccumulator {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut results = ResultAccumulator::new();
+
+    for _ in 0..iterations {
+        deck.shuffle_with(&mut rng);
+        let round = deck.deal_round();
+        let mut dealer = [0; HAND_SIZE];
+        dealer.copy_from_slice(&round.dealer_cards()[..HAND_SIZE]);
+
+        let mut context = RoundContext::new(round.player_hands(), round.dealer_cards(), &GameConfig::STANDARD);
+        for player in round.player_hands().iter() {
+            let net = if strategy.plays(player, &context) { compare_hands(player, &dealer).net_antes() } else { -1 };
+            results.push(net);
+            context.reveal(player);
+        }
+    }
+
+    results
+}
+
+/// Nearest-rank percentiles of a result distribution, with each p given in percent (e.g. 95.0).
+/// Empty when the distribution holds no results, since no percentile exists
+pub fn result_percentiles(dist: &BTreeMap<i32, u64>, ps: &[f64]) -> Vec<i32> {
+    let total: u64 = dist.values().sum();
+    let Some(&largest) = dist.iter().rev().find(|&(_, &count)| count > 0).map(|(result, _)| result) else {
+        return Vec::new();
+    };
+    ps.iter()
+        .map(|&p| {
+            let rank = ((p / 100.0 * total as f64).ceil() as u64).max(1);
+            let mut seen = 0;
+            for (&result, &count) in dist.iter() {
+                seen += count;
+                if seen >= rank {
+                    return result;
+                }
+            }
+            largest
+        })
+        .collect()
+}
+
+/// simulate_strategy_seeded() by importance sampling, for tight estimates on rare long flushes.
+/// Each sample is one random seat whose suit pattern is drawn with its exact probability times
+/// bias^(flush length - 7), so long flushes are oversampled with no deals thrown away. The rest of
+/// the table is dealt uniformly from the other 45 cards, and each result is reweighted by the mean
+/// of that factor over it so the edge per hand stays unbiased. bias = 1.0 is plain uniform sampling
+pub fn simulate_importance(strategy: &dyn Strategy, iterations: usize, seed: u64, bias: f64) -> f64 {
+    let samples = importance_samples(strategy, iterations, seed, bias);
+    samples.iter().sum::<f64>() / iterations as f64
+}
+
+/// Reweighted result of each simulate_importance() sample, whose mean is the edge per hand
+pub(crate) fn importance_samples(strategy: &dyn Strategy, iterations: usize, seed: u64, bias: f64) -> Vec<f64> {
+    assert!(bias >= 1.0, "bias must be at least 1.0, got {}", bias);
+    let keep = |longest: usize| bias.powi(longest as i32 - HAND_SIZE as i32);
+
+    // Every suit pattern of a 7-card hand, each weighted by its share of C(52, 7) times keep()
+    let total = n_choose_k(DECK_SIZE as u64, HAND_SIZE as u64) as f64;
+    let mut patterns = Vec::new();
+    for a in 0..=HAND_SIZE {
+        for b in 0..=HAND_SIZE - a {
+            for c in 0..=HAND_SIZE - a - b {
+                let pattern = [a, b, c, HAND_SIZE - a - b - c];
+                let hands: u64 = pattern.iter().map(|&count| n_choose_k(RANKS as u64, count as u64)).product();
+                let longest = *pattern.iter().max().expect("four suits");
+                patterns.push((pattern, hands as f64 / total * keep(longest)));
+            }
+        }
+    }
+    let mean_keep: f64 = patterns.iter().map(|&(_, weight)| weight).sum();
+    let pick = WeightedIndex::new(patterns.iter().map(|&(_, weight)| weight)).expect("every suit pattern has a positive weight");
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut players_hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_hand = [0; DEALER_CARDS];
+    let mut rest = Vec::with_capacity(DECK_SIZE - HAND_SIZE);
+
+    (0..iterations)
+        .map(|_| {
+            let (pattern, _) = patterns[pick.sample(&mut rng)];
+            let seat = rng.gen_range(0..NUM_PLAYERS);
+            let mut held = 0;
+            for (suit, &count) in pattern.iter().enumerate() {
+                for rank in index::sample(&mut rng, RANKS, count) {
+                    players_hands[seat][held] = suit * RANKS + rank;
+                    held += 1;
+                }
+            }
+            rest.clear();
+            rest.extend((0..DECK_SIZE).filter(|card| !players_hands[seat].contains(card)));
+            rest.shuffle(&mut rng);
+            let mut others = rest.chunks_exact(HAND_SIZE);
+            for (other, hand) in players_hands.iter_mut().enumerate() {
+                if other != seat {
+                    hand.copy_from_slice(others.next().expect("45 cards cover the other 5 seats"));
+                }
+            }
+            dealer_hand.copy_from_slice(&rest[(NUM_PLAYERS - 1) * HAND_SIZE..]);
+
+            let player = &players_hands[seat];
+            let mut context = RoundContext::new(&players_hands, &dealer_hand, &GameConfig::STANDARD);
+            for earlier in players_hands[..seat].iter() {
+                context.reveal(earlier);
+            }
+            let net = if strategy.plays(player, &context) {
+                tally_average_result(player, &dealer_hand, &GameConfig::STANDARD).0
+            } else {
+                -GameConfig::STANDARD.fold_cost
+            };
+            let longest = get_best_flush_masked(hand_mask(player)).len();
+            net * mean_keep / keep(longest)
+        })
+        .collect()
+}
+
+/// Mean of (candidate-pool average - direct estimate) for seat 0 over seeded deals. The direct
+/// estimate draws many real dealer hands from the 45 cards outside seat 0's hand, ignoring what the
+/// other seats hold, so the difference isolates what conditioning on the 10-card pool changes
+pub fn validate_pool_method(seed: u64, trials: usize) -> f64 {
+    const DIRECT_SAMPLES: usize = 1000;
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut discrepancy = 0.0;
+
+    for _ in 0..trials {
+        deck.shuffle_with(&mut rng);
+        let round = deck.deal_round();
+        let player = round.player(0);
+        let pooled = calculate_average_result(player, round.dealer_cards());
+
+        discrepancy += pooled - direct_ev(player, DIRECT_SAMPLES, &mut rng);
+    }
+
+    discrepancy / trials as f64
+}
+
+/// Average result of playing a hand known to hold the given cards of one suit, over random
+/// completions of the rest of the hand and random dealer candidate pools. An error if the known
+/// cards can't be part of one hand's flush or no iterations are asked for
+pub fn conditional_ev(known_suited: &[usize], iterations: usize, seed: u64) -> Result<f64, String> {
+    if known_suited.len() > HAND_SIZE {
+        return Err(format!("at most {} known cards, got {}", HAND_SIZE, known_suited.len()));
+    }
+    if let Some(&card) = known_suited.iter().find(|&&card| card >= DECK_SIZE) {
+        return Err(format!("known cards must come from the deck, got {}", card));
+    }
+    if let Some(card) = repeated_card(known_suited.iter()) {
+        return Err(format!("known cards must be distinct, {} appears more than once", card_name(card)));
+    }
+    if !known_suited.iter().all(|&card| Suit::of(card) == Suit::of(known_suited[0])) {
+        return Err(format!("known cards must share a suit, got {:?}", vec_to_strings(known_suited)));
+    }
+    if iterations == 0 {
+        return Err("conditional_ev needs at least one iteration".to_string());
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut rest: Vec<usize> = (0..DECK_SIZE).filter(|card| !known_suited.contains(card)).collect();
+    let missing = HAND_SIZE - known_suited.len();
+    let mut hand = [0; HAND_SIZE];
+    hand[..known_suited.len()].copy_from_slice(known_suited);
+    let mut dealer_cards = [0; DEALER_CARDS];
+    let mut total = 0.0;
+
+    for _ in 0..iterations {
+        rest.shuffle(&mut rng);
+        hand[known_suited.len()..].copy_from_slice(&rest[..missing]);
+        dealer_cards.copy_from_slice(&rest[missing..missing + DEALER_CARDS]);
+        total += calculate_average_result(&hand, &dealer_cards);
+    }
+
+    Ok(total / iterations as f64)
+}
+
+/// Average result of playing a hand whose best flush is 3 cards topped by high_rank (0 = 2 up to 12 = Ace),
+/// over random hands with that best flush and random dealer candidate pools. Compare it with the -1.0 of
+/// folding to find the rank a 3-card flush should play from
+pub fn three_card_ev(high_rank: usize, iterations: usize, seed: u64) -> Result<f64, String> {
+    if !(2..RANKS).contains(&high_rank) {
+        return Err(format!("a 3-card flush is topped by rank 2 (a 4) to 12 (an Ace), got {}", high_rank));
+    }
+    if iterations == 0 {
+        return Err("three_card_ev needs at least one iteration".to_string());
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut hand = [0; HAND_SIZE];
+    let mut dealer_cards = [0; DEALER_CARDS];
+    let mut total = 0.0;
+    let mut sampled = 0;
+
+    while sampled < iterations {
+        deck.shuffle_with(&mut rng);
+        hand.copy_from_slice(&deck.cards[..HAND_SIZE]);
+        let flush = get_best_flush(&hand);
+        if flush.len() != 3 || Rank::of(flush[0]).to_index() != high_rank {
+            continue;
+        }
+        dealer_cards.copy_from_slice(&deck.cards[HAND_SIZE..HAND_SIZE + DEALER_CARDS]);
+        total += calculate_average_result(&hand, &dealer_cards);
+        sampled += 1;
+    }
+
+    Ok(total / iterations as f64)
+}
+
+/// Average compare_hands() result against dealer hands drawn at random from the cards the hand doesn't hold
+pub fn direct_ev<R: Rng + ?Sized>(hand: &[usize; HAND_SIZE], samples: usize, rng: &mut R) -> f64 {
+    let mut unseen: Vec<usize> = (0..DECK_SIZE).filter(|card| !hand.contains(card)).collect();
+    let mut dealer = [0; HAND_SIZE];
+    let mut total = 0.0;
+    for _ in 0..samples {
+        unseen.shuffle(rng);
+        dealer.copy_from_slice(&unseen[..HAND_SIZE]);
+        total += compare_hands(hand, &dealer).net_antes() as f64;
+    }
+    total / samples as f64
+}
+
+/// simulate_strategy_seeded() with antithetic variates: every shuffled deck is dealt a second time
+/// with the roles swapped, the dealer's 10 candidates moved to the front for the first seats and
+/// the last 10 player cards left as the dealer's pool. The swapped deck is just as random, so the
+/// estimate stays unbiased, while a pool that beat the table now sits with the players, so a pair's
+/// results are negatively correlated (about -0.15 per deal for Jacobson) and their average varies
+/// less than two independent deals. A rank or suit mirror keeps every flush length and correlates
+/// the pair positively instead. An odd iteration count is rounded up to whole pairs, and fewer than
+/// 2 still plays one pair
+pub fn simulate_antithetic(strategy: &dyn Strategy, iterations: usize, seed: u64) -> f64 {
+    let pairs = deal_pairs(strategy, iterations.div_ceil(2).max(1), seed, true);
+    pairs.iter().map(|(first, second)| (first + second) / 2.0).sum::<f64>() / pairs.len() as f64
+}
+
+/// Per-hand result of both deals in each pair, the second either the first with the dealer's
+/// and players' roles swapped (antithetic) or an independent shuffle
+pub(crate) fn deal_pairs(strategy: &dyn Strategy, pairs: usize, seed: u64, antithetic: bool) -> Vec<(f64, f64)> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut players_hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_hand = [0; DEALER_CARDS];
+
+    (0..pairs)
+        .map(|_| {
+            let (mut first, mut second) = (SimulationResult::new(), SimulationResult::new());
+            deck.shuffle_with(&mut rng);
+            deck.deal_into(&mut players_hands, &mut dealer_hand);
+            play_round(strategy, &GameConfig::STANDARD, DealerDraw::CandidatePool, &players_hands, &dealer_hand, &mut first);
+            if antithetic {
+                deck.cards.rotate_right(DEALER_CARDS);
+            } else {
+                deck.shuffle_with(&mut rng);
+            }
+            deck.deal_into(&mut players_hands, &mut dealer_hand);
+            play_round(strategy, &GameConfig::STANDARD, DealerDraw::CandidatePool, &players_hands, &dealer_hand, &mut second);
+            (first.per_hand(), second.per_hand())
+        })
+        .collect()
+}
+
+/// Mean per-hand edge of strategy a over strategy b on the same seeded deals as
+/// simulate_strategy_seeded(), with the two-sided p-value of a paired t-test. Seats at one table
+/// share a dealer, so each deal's difference averaged over the seats is one pair. The p-value uses
+/// the normal approximation to the t distribution, close once there are a few hundred deals
+pub fn paired_significance(a: &dyn Strategy, b: &dyn Strategy, iterations: usize, seed: u64) -> (f64, f64) {
+    assert!(iterations > 1, "a paired test needs at least 2 deals, got {}", iterations);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut players_hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_hand = [0; DEALER_CARDS];
+    let (mut sum, mut sum_squares) = (0.0, 0.0);
+
+    for _ in 0..iterations {
+        deck.shuffle_with(&mut rng);
+        deck.deal_into(&mut players_hands, &mut dealer_hand);
+        let (mut totals_a, mut totals_b) = (SimulationResult::new(), SimulationResult::new());
+        play_round(a, &GameConfig::STANDARD, DealerDraw::CandidatePool, &players_hands, &dealer_hand, &mut totals_a);
+        play_round(b, &GameConfig::STANDARD, DealerDraw::CandidatePool, &players_hands, &dealer_hand, &mut totals_b);
+        let difference = totals_a.per_hand() - totals_b.per_hand();
+        sum += difference;
+        sum_squares += difference * difference;
+    }
+
+    let n = iterations as f64;
+    let mean = sum / n;
+    let std_err = ((sum_squares - sum * sum / n) / (n - 1.0)).max(0.0).sqrt() / n.sqrt();
+    let p_value = match std_err {
+        0.0 if mean == 0.0 => 1.0,
+        0.0 => 0.0,
+        _ => two_sided_p_value(mean / std_err),
+    };
+    (mean, p_value)
+}
+
+/// Chance a standard normal lands at least |z| from 0, from the Abramowitz and Stegun 7.1.26
+/// approximation to erfc (absolute error below 1.5e-7)
+pub(crate) fn two_sided_p_value(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    poly * (-x * x).exp()
+}
@@ -0,0 +1,79 @@
+//! Timing benchmarks
+
+use std::time::Duration;
+use rand_chacha::ChaCha8Rng;
+use rand::SeedableRng;
+use crate::card::{DEALER_CARDS, HAND_SIZE, NUM_PLAYERS};
+use crate::deck::Deck;
+use crate::evaluator::{best_flush_lengths, get_best_flush, table_results};
+
+/// Times dealing a table by value against dealing into reused buffers, returning both in that order
+pub fn bench_dealing(iterations: usize) -> (Duration, Duration) {
+    let deck = Deck::new(None);
+    let start = std::time::Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(std::hint::black_box(&deck).get_player_hands());
+        std::hint::black_box(std::hint::black_box(&deck).get_dealer_cards());
+    }
+    let by_value = start.elapsed();
+
+    let mut hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_cards = [0; DEALER_CARDS];
+    let start = std::time::Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(&deck).deal_into(&mut hands, &mut dealer_cards);
+        std::hint::black_box((&hands, &dealer_cards));
+    }
+    let into_buffers = start.elapsed();
+
+    (by_value, into_buffers)
+}
+
+/// Times calling get_best_flush() on each hand against batch flush lengths, returning both in that
+/// order, or an error if the two disagree on any hand
+pub fn bench_flush_lengths(num_hands: usize) -> Result<(Duration, Duration), String> {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut deck = Deck::ordered();
+    let mut hands = Vec::with_capacity(num_hands);
+    while hands.len() < num_hands {
+        deck.shuffle_with(&mut rng);
+        hands.extend(deck.get_player_hands());
+    }
+    hands.truncate(num_hands);
+
+    let start = std::time::Instant::now();
+    let looped: Vec<usize> = hands.iter().map(|hand| get_best_flush(hand).len()).collect();
+    let per_hand = start.elapsed();
+
+    let start = std::time::Instant::now();
+    let batched = std::hint::black_box(best_flush_lengths(std::hint::black_box(&hands)));
+    let batch = start.elapsed();
+
+    match looped.iter().zip(batched.iter()).position(|(looped, batched)| looped != batched) {
+        Some(index) => Err(format!("batch flush length of hand {} is {}, get_best_flush gives {}", index, batched[index], looped[index])),
+        None => Ok((per_hand, batch)),
+    }
+}
+
+/// Times seeded deals through table_results(), for a regression guard on simulation throughput.
+/// A release build takes about 1.0s for 100k deals; callers should allow several times that so only
+/// catastrophic slowdowns trip the guard, such as losing the shared dealer enumeration or going back
+/// to a HashMap per flush
+pub fn bench_throughput(iterations: usize) -> Duration {
+    let mut rng = ChaCha8Rng::seed_from_u64(145);
+    let mut deck = Deck::ordered();
+    let mut hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_cards = [0; DEALER_CARDS];
+    let mut total = 0.0;
+
+    let start = std::time::Instant::now();
+    for _ in 0..iterations {
+        deck.shuffle_with(&mut rng);
+        deck.deal_into(&mut hands, &mut dealer_cards);
+        total += table_results(&hands, &dealer_cards).iter().sum::<f64>();
+    }
+    let elapsed = start.elapsed();
+
+    std::hint::black_box(total);
+    elapsed
+}
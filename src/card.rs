@@ -0,0 +1,250 @@
+//! Card values, names and parsing
+
+// Use of constants instead of random numbers so operations are easier to correspond to the operations
+pub const RANKS: usize = 13; // 13 ranks (card values)
+pub const SUITS: usize = 4;  // 4 suits
+pub const DECK_SIZE: usize = 52; // 52 Cards in deck
+pub const HAND_SIZE: usize = 7; // 7 cards per hand
+pub const NUM_PLAYERS: usize = 6; // 6 player team
+pub const DEALER_CARDS: usize = 10; // 10 remaining unkown possible dealer cards
+pub const WILD_CARD: usize = 52; // Joker for the wild card variant, only counted when GameConfig::wild_cards is set
+
+/// Card ranks from 2 up to Ace, in the order of card % RANKS
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+impl Rank {
+    pub const ALL: [Rank; RANKS] = [
+        Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven, Rank::Eight,
+        Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+    ];
+    /// The rank at this index (0 for a 2 up to 12 for an Ace), or None past the Ace
+    pub fn from_index(index: usize) -> Option<Rank> {
+        Rank::ALL.get(index).copied()
+    }
+    pub fn to_index(self) -> usize {
+        self as usize
+    }
+    /// Rank of a card integer (0-51)
+    pub fn of(card: usize) -> Rank {
+        Rank::ALL[card % RANKS]
+    }
+    /// The rank written as c in card strings, '2'-'9', 'T', 'J', 'Q', 'K' or 'A'
+    pub fn from_char(c: char) -> Option<Rank> {
+        "23456789TJQKA".find(c).and_then(Rank::from_index)
+    }
+}
+impl std::fmt::Display for Rank {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", "23456789TJQKA".as_bytes()[self.to_index()] as char)
+    }
+}
+
+/// Suits in the order of card / RANKS, which is also their rank for TieBreak::SuitRank
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suit {
+    Diamonds,
+    Clubs,
+    Hearts,
+    Spades,
+}
+impl Suit {
+    pub const ALL: [Suit; SUITS] = [Suit::Diamonds, Suit::Clubs, Suit::Hearts, Suit::Spades];
+    /// The suit at this index (0-3 for d, c, h, s), or None past spades
+    pub fn from_index(index: usize) -> Option<Suit> {
+        Suit::ALL.get(index).copied()
+    }
+    pub fn to_index(self) -> usize {
+        self as usize
+    }
+    /// Suit of a card integer (0-51), or None for a joker or anything else outside the deck
+    pub fn of(card: usize) -> Option<Suit> {
+        if card < DECK_SIZE { Suit::from_index(card / RANKS) } else { None }
+    }
+    /// The suit written as c in card strings, 'd', 'c', 'h' or 's'
+    pub fn from_char(c: char) -> Option<Suit> {
+        "dchs".find(c).and_then(Suit::from_index)
+    }
+}
+impl std::fmt::Display for Suit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", "dchs".as_bytes()[self.to_index()] as char)
+    }
+}
+
+/// A card known to be in the deck, 0-51 as above. The joker (WILD_CARD) is not a Card
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Card(u8);
+impl Card {
+    /// The card with this integer value, or None outside 0-51
+    pub fn new(n: usize) -> Option<Card> {
+        if n < DECK_SIZE { Some(Card(n as u8)) } else { None }
+    }
+    /// 0 for a 2 up to 12 for an Ace
+    pub fn rank(self) -> u8 {
+        self.0 % RANKS as u8
+    }
+    /// 0-3 for diamonds, clubs, hearts, spades
+    pub fn suit(self) -> u8 {
+        self.0 / RANKS as u8
+    }
+}
+impl From<Card> for usize {
+    fn from(card: Card) -> usize {
+        card.0 as usize
+    }
+}
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", tostr(*self))
+    }
+}
+
+/// Converts a card to its string representation, e.g. "As"
+pub fn tostr(card: Card) -> String {
+    format!("{}{}", Rank::ALL[card.rank() as usize], Suit::ALL[card.suit() as usize])
+}
+
+/// tostr() for a card integer (0-51), "Jo" for WILD_CARD or "??" for anything else
+pub fn card_name(card: usize) -> String {
+    match Card::new(card) {
+        Some(card) => tostr(card),
+        None if card == WILD_CARD => "Jo".to_string(),
+        None => "??".to_string(),
+    }
+}
+
+/// Why a card string could not be parsed
+#[derive(Debug, PartialEq)]
+pub enum ParseCardError {
+    WrongLength(String),
+    BadRank(char),
+    BadSuit(char),
+}
+impl std::fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseCardError::WrongLength(s) => write!(f, "card \"{}\" should be a rank and a suit, e.g. As", s),
+            ParseCardError::BadRank(c) => write!(f, "invalid rank '{}'", c),
+            ParseCardError::BadSuit(c) => write!(f, "invalid suit '{}'", c),
+        }
+    }
+}
+impl std::error::Error for ParseCardError {}
+
+/// Why a hand's card strings could not be parsed
+#[derive(Debug, PartialEq)]
+pub enum ParseHandError {
+    Card(ParseCardError),
+    WrongHandSize(usize),
+    DuplicateCard(usize),
+}
+impl From<ParseCardError> for ParseHandError {
+    fn from(e: ParseCardError) -> Self {
+        ParseHandError::Card(e)
+    }
+}
+impl std::fmt::Display for ParseHandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseHandError::Card(e) => write!(f, "{}", e),
+            ParseHandError::WrongHandSize(n) => write!(f, "a hand has {} cards, got {}", HAND_SIZE, n),
+            ParseHandError::DuplicateCard(card) => write!(f, "{} appears more than once", card_name(*card)),
+        }
+    }
+}
+impl std::error::Error for ParseHandError {}
+
+/// Parses a rank and a suit like "As" or "Td", the inverse of tostr()
+impl std::str::FromStr for Card {
+    type Err = ParseCardError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        if chars.len() != 2 {
+            return Err(ParseCardError::WrongLength(s.to_string()));
+        }
+        let rank = Rank::from_char(chars[0]).ok_or(ParseCardError::BadRank(chars[0]))?;
+        let suit = Suit::from_char(chars[1]).ok_or(ParseCardError::BadSuit(chars[1]))?;
+        Ok(Card((suit.to_index() * RANKS + rank.to_index()) as u8))
+    }
+}
+
+/// Converts a string like "As" or "Td" back to its card integer (0-51)
+pub fn parse_card(s: &str) -> Result<usize, ParseCardError> {
+    s.parse::<Card>().map(usize::from)
+}
+
+/// parse_card() that also reads "Jo" as WILD_CARD, the inverse of card_name()
+pub fn parse_card_or_joker(s: &str) -> Result<usize, ParseCardError> {
+    // "Jo" reads as a Jack of no suit, so only its suit character fails to parse
+    match parse_card(s) {
+        Err(ParseCardError::BadSuit('o')) if s == "Jo" => Ok(WILD_CARD),
+        parsed => parsed,
+    }
+}
+
+/// Builds a 7-card hand from card strings, e.g. ["As", "Ks", "Qs", "Js", "Ts", "2d", "3c"]. "Jo" is
+/// a joker for the wild card variant
+pub fn hand_from_strs(strs: &[&str]) -> Result<[usize; HAND_SIZE], ParseHandError> {
+    let cards = strs.iter().map(|s| parse_card_or_joker(s)).collect::<Result<Vec<usize>, _>>()?;
+    cards.try_into().map_err(|cards: Vec<usize>| ParseHandError::WrongHandSize(cards.len()))
+}
+
+/// A player's 7 cards, parsed from text like "As Ks Qs Js Ts 2d 3c"
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayerHand(pub [usize; HAND_SIZE]);
+impl TryFrom<&str> for PlayerHand {
+    type Error = ParseHandError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let cards = hand_from_strs(&s.split_whitespace().collect::<Vec<&str>>())?;
+        match repeated_card(cards.iter()) {
+            Some(card) => Err(ParseHandError::DuplicateCard(card)),
+            None => Ok(PlayerHand(cards)),
+        }
+    }
+}
+
+/// Converts an array of card integers to an array of string representations
+pub(crate) fn arr_to_strings<const N: usize>(cards: &[usize; N]) -> [String; N] {
+    let mut result = std::array::from_fn(|_| String::new());
+    for i in 0..N {
+        result[i] = card_name(cards[i]);
+    }
+    result
+}
+pub fn vec_to_strings(cards: &[usize]) -> Vec<String> {
+    cards.iter().map(|&card| card_name(card)).collect()
+}
+
+/// Adds each card's suit to counts indexed by suit (d, c, h, s), jokers have no suit and are left out
+pub(crate) fn add_suit_counts<'a, I: IntoIterator<Item = &'a usize>>(counts: &mut [usize; SUITS], cards: I) {
+    for suit in cards.into_iter().filter_map(|&card| Suit::of(card)) {
+        counts[suit.to_index()] += 1;
+    }
+}
+
+/// First card that shows up a second time, if any. Cards index bits of a u64, so callers check them
+/// against DECK_SIZE (or WILD_CARD) first
+pub(crate) fn repeated_card<'a, I: Iterator<Item = &'a usize>>(cards: I) -> Option<usize> {
+    let mut seen = 0u64;
+    for &card in cards {
+        if seen & 1 << card != 0 {
+            return Some(card);
+        }
+        seen |= 1 << card;
+    }
+    None
+}
@@ -0,0 +1,322 @@
+//! Shuffling and dealing
+
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use rand_chacha::ChaCha8Rng;
+use rand::{Rng, SeedableRng};
+use crate::card::{DEALER_CARDS, DECK_SIZE, HAND_SIZE, NUM_PLAYERS, arr_to_strings, parse_card, repeated_card, card_name};
+use crate::evaluator::{DealerPool, hand_mask};
+use crate::rules::GameConfig;
+
+/// Deck struct for 52 card deck functions
+/// To replay the same deal for several strategies, take a snapshot() after shuffling
+/// and restore() it before each run instead of reshuffling
+#[derive(Clone, Debug)]
+pub struct Deck {
+    pub(crate) cards: [usize; DECK_SIZE],
+    // Index of the next card deal_hand() hands out
+    pub(crate) cursor: usize,
+    // Cards in play, the first size of cards. A reduced deck keeps the cards it leaves out after
+    // them, so cards stays a permutation of all 52
+    pub(crate) size: usize,
+}
+impl Deck {
+    pub fn new(existing_deck: Option<[usize; DECK_SIZE]>) -> Self {
+        match existing_deck {
+            Some(cards) => {
+                let deck = Deck { cards, cursor: 0, size: DECK_SIZE };
+                debug_assert!(deck.is_permutation(), "deck must hold each of the 52 cards once");
+                deck
+            }
+            None => {
+                let mut deck = Deck::ordered();
+                deck.shuffle();
+                deck
+            }
+        }
+    }
+    /// Deck in the given order, checked to hold each of the 52 cards once
+    pub fn try_from_cards(cards: [usize; DECK_SIZE]) -> Result<Self, DealError> {
+        if let Some(&card) = cards.iter().find(|&&card| card >= DECK_SIZE) {
+            return Err(DealError::NotInDeck(card));
+        }
+        match repeated_card(cards.iter()) {
+            Some(card) => Err(DealError::DuplicateCard(card)),
+            None => Ok(Deck { cards, cursor: 0, size: DECK_SIZE }),
+        }
+    }
+    /// Deck from named cards in deal order, e.g. to reproduce a published deal
+    pub fn from_card_strings(strs: &[&str]) -> Result<Self, Box<dyn std::error::Error>> {
+        let cards = strs.iter().map(|s| parse_card(s)).collect::<Result<Vec<usize>, _>>()?;
+        let cards: [usize; DECK_SIZE] = cards
+            .try_into()
+            .map_err(|cards: Vec<usize>| format!("a deck has {} cards, got {}", DECK_SIZE, cards.len()))?;
+        Ok(Deck::try_from_cards(cards)?)
+    }
+    /// Deck shuffled from the seed, so a run that deals from it can be replayed hand for hand. Keep
+    /// reshuffling it with shuffle_with() and a ChaCha8Rng of the same seed to replay a whole loop.
+    /// ChaCha8Rng rather than StdRng, whose stream may change between rand releases and so would break
+    /// the golden file
+    pub fn new_seeded(seed: u64) -> Self {
+        let mut deck = Deck::ordered();
+        deck.shuffle_with(&mut ChaCha8Rng::seed_from_u64(seed));
+        deck
+    }
+    /// Deck shuffled from a seed derived from both arguments, so iteration i of a run deals the same
+    /// cards whichever thread plays it and whatever ran before it
+    pub fn new_for_iteration(base_seed: u64, iteration: u64) -> Self {
+        let mut deck = Deck::ordered();
+        deck.shuffle_with(&mut ChaCha8Rng::seed_from_u64(iteration_seed(base_seed, iteration)));
+        deck
+    }
+    /// Unshuffled deck with every card at the index of its own value
+    pub fn ordered() -> Self {
+        Deck { cards: std::array::from_fn(|i| i), cursor: 0, size: DECK_SIZE }
+    }
+    /// Deck of only the given cards, e.g. a 36-card deck without 2-5. Cards keep their usual values
+    /// so evaluation and card_name() work unchanged; deal with deal_hand() or try_deal_round(), since a
+    /// full table needs all 52 cards and the full-table methods panic rather than deal a left out card
+    pub fn from_subset(subset: &[usize]) -> Result<Self, DealError> {
+        if let Some(&card) = subset.iter().find(|&&card| card >= DECK_SIZE) {
+            return Err(DealError::NotInDeck(card));
+        }
+        if let Some(card) = repeated_card(subset.iter()) {
+            return Err(DealError::DuplicateCard(card));
+        }
+        let mask = hand_mask(subset);
+        let left_out = (0..DECK_SIZE).filter(|card| mask & (1 << card) == 0);
+        let mut cards = [0; DECK_SIZE];
+        for (slot, card) in cards.iter_mut().zip(subset.iter().copied().chain(left_out)) {
+            *slot = card;
+        }
+        Ok(Deck { cards, cursor: 0, size: subset.len() })
+    }
+    /// Cards in play, DECK_SIZE unless built by from_subset()
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    /// Index where the card currently sits in the deck
+    pub fn position_of(&self, card: usize) -> usize {
+        self.cards.iter().position(|&c| c == card).expect("card is not in the deck")
+    }
+    /// True if every card 0-51 appears exactly once
+    pub fn is_permutation(&self) -> bool {
+        hand_mask(&self.cards) == (1 << DECK_SIZE) - 1
+    }
+    pub fn shuffle(&mut self) {
+        let mut rng = thread_rng();
+        self.shuffle_with(&mut rng);
+    }
+    /// Shuffles with a caller-provided random source, e.g. a seeded ChaCha8Rng or a `&mut dyn RngCore`
+    /// such as ScriptedRng when a test needs an exact deal. Dealing restarts from the top
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards[..self.size].shuffle(rng);
+        self.cursor = 0;
+    }
+    /// The next n cards from the top of the deck, or None if fewer than n are left
+    pub fn deal_hand(&mut self, n: usize) -> Option<&[usize]> {
+        let start = self.cursor;
+        if start + n > self.size {
+            return None;
+        }
+        self.cursor += n;
+        Some(&self.cards[start..self.cursor])
+    }
+    /// Puts every card back for deal_hand() without changing the order
+    pub fn reset(&mut self) {
+        self.cursor = 0;
+    }
+    pub fn get_cards(&self) -> [usize; DECK_SIZE] {
+        self.cards
+    }
+    /// Saves the current card order
+    pub fn snapshot(&self) -> Deck {
+        self.clone()
+    }
+    /// Puts the cards back in the order of an earlier snapshot
+    pub fn restore(&mut self, snapshot: &Deck) {
+        self.cards = snapshot.cards;
+        self.cursor = snapshot.cursor;
+        self.size = snapshot.size;
+    }
+    /// Every seat's hand as deal_into() deals it. Panics on a reduced deck, see deal_into()
+    pub fn get_player_hands(&self) -> [[usize; HAND_SIZE]; NUM_PLAYERS] {
+        let mut hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+        self.deal_into(&mut hands, &mut [0; DEALER_CARDS]);
+        hands
+    }
+    /// One seat's hand, the cards deal_into() would give it. Panics if the seat's cards run past the
+    /// cards in play, which only a reduced deck can make happen
+    pub fn seat_hand(&self, seat: usize) -> [usize; HAND_SIZE] {
+        assert!(seat < NUM_PLAYERS, "seat must be below {}, got {}", NUM_PLAYERS, seat);
+        assert!(
+            (seat + 1) * HAND_SIZE <= self.size,
+            "seat {} needs {} cards but the deck holds {}", seat, (seat + 1) * HAND_SIZE, self.size
+        );
+        let mut hand = [0; HAND_SIZE];
+        hand.copy_from_slice(&self.cards[seat * HAND_SIZE..(seat + 1) * HAND_SIZE]);
+        hand
+    }
+    /// The dealer's candidate cards as deal_into() deals them. Panics on a reduced deck, see deal_into()
+    pub fn get_dealer_cards(&self) -> [usize; DEALER_CARDS] {
+        let mut dealer_cards = [0; DEALER_CARDS];
+        self.deal_into(&mut [[0; HAND_SIZE]; NUM_PLAYERS], &mut dealer_cards);
+        dealer_cards
+    }
+    /// Deals every seat and the dealer's candidate cards as a Round. Panics on a reduced deck, see
+    /// deal_into() and try_deal_round()
+    pub fn deal_round(&self) -> Round {
+        let mut round = Round::new([[0; HAND_SIZE]; NUM_PLAYERS], [0; DEALER_CARDS]);
+        self.deal_into(&mut round.player_hands, &mut round.dealer_cards);
+        round
+    }
+    /// deal_round() from the cards deal_hand() has not handed out yet, checked instead of assuming a
+    /// full deck: an error if too few cards are left or the result is not a valid table, in which
+    /// case the deck is left as it was
+    pub fn try_deal_round(&mut self) -> Result<Round, DealError> {
+        let needed = NUM_PLAYERS * HAND_SIZE + DEALER_CARDS;
+        let remaining = self.size - self.cursor;
+        if remaining < needed {
+            return Err(DealError::NotEnoughCards { needed, remaining });
+        }
+        let cards = &self.cards[self.cursor..self.cursor + needed];
+        let mut round = Round::new([[0; HAND_SIZE]; NUM_PLAYERS], [0; DEALER_CARDS]);
+        for (hand, hand_cards) in round.player_hands.iter_mut().zip(cards.chunks_exact(HAND_SIZE)) {
+            hand.copy_from_slice(hand_cards);
+        }
+        round.dealer_cards.copy_from_slice(&cards[NUM_PLAYERS * HAND_SIZE..]);
+        round.validate()?;
+        self.cursor += needed;
+        Ok(round)
+    }
+    /// Deals the player hands and dealer cards into existing buffers, so simulation loops can reuse them.
+    /// A full table takes all 52 cards, so this panics on a reduced deck instead of dealing the cards
+    /// from_subset() left out; try_deal_round() reports that as an error
+    pub fn deal_into(&self, hands: &mut [[usize; HAND_SIZE]; NUM_PLAYERS], dealer_cards: &mut [usize; DEALER_CARDS]) {
+        assert_eq!(self.size, DECK_SIZE, "a full table needs the whole deck, use try_deal_round()");
+        for (hand, cards) in hands.iter_mut().zip(self.cards.chunks_exact(HAND_SIZE)) {
+            hand.copy_from_slice(cards);
+        }
+        dealer_cards.copy_from_slice(&self.cards[NUM_PLAYERS * HAND_SIZE..NUM_PLAYERS * HAND_SIZE + DEALER_CARDS]);
+    }
+    /// Every seat's hand and the dealer's candidate cards for a game dealing config.hand_size cards
+    /// to each seat, from the top of the deck like deal_into(): the seats in order, then the
+    /// DEALER_CARDS pool. An error if the cards in play can't cover the round
+    pub fn deal_round_with(&self, config: &GameConfig) -> Result<(Vec<&[usize]>, &[usize]), DealError> {
+        let seats = NUM_PLAYERS * config.hand_size;
+        let needed = seats + DEALER_CARDS;
+        if needed > self.size {
+            return Err(DealError::NotEnoughCards { needed, remaining: self.size });
+        }
+        let hands = (0..NUM_PLAYERS).map(|seat| &self.cards[seat * config.hand_size..(seat + 1) * config.hand_size]).collect();
+        Ok((hands, &self.cards[seats..needed]))
+    }
+    /// Cards in play that deal_round_with() leaves over for the config, or past whatever deal_hand()
+    /// and try_deal_round() have handed out if that is further. Empty for the standard game, while
+    /// smaller hands leave the rest of the deck here
+    pub fn undealt(&self, config: &GameConfig) -> &[usize] {
+        let round = NUM_PLAYERS * config.hand_size + DEALER_CARDS;
+        &self.cards[round.max(self.cursor).min(self.size)..self.size]
+    }
+}
+
+/// Deck from its 52 cards in deal order as space-separated text, see Deck::from_card_strings()
+impl TryFrom<&str> for Deck {
+    type Error = Box<dyn std::error::Error>;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Deck::from_card_strings(&s.split_whitespace().collect::<Vec<&str>>())
+    }
+}
+
+/// Splits a base seed into independent per-iteration seeds with the splitmix64 mixer, so nearby
+/// base seeds and iterations still give unrelated shuffles
+fn iteration_seed(base_seed: u64, iteration: u64) -> u64 {
+    let mix = |mut z: u64| {
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    };
+    mix(mix(base_seed).wrapping_add(iteration.wrapping_add(1).wrapping_mul(0x9E3779B97F4A7C15)))
+}
+
+/// True if every card is in the deck and none is dealt twice across the player hands and the
+/// dealer's candidate cards
+pub fn deal_is_disjoint(hands: &[[usize; HAND_SIZE]; NUM_PLAYERS], dealer_cards: &[usize; DEALER_CARDS]) -> bool {
+    hands.iter().flatten().chain(dealer_cards.iter()).all(|&card| card < DECK_SIZE)
+        && repeated_card(hands.iter().flatten().chain(dealer_cards.iter())).is_none()
+}
+
+/// Why a dealt round is not a valid table
+#[derive(Debug, PartialEq)]
+pub enum DealError {
+    DuplicateCard(usize),
+    NotInDeck(usize),
+    // The deal needs more cards than are left to deal
+    NotEnoughCards { needed: usize, remaining: usize },
+}
+impl std::fmt::Display for DealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DealError::DuplicateCard(card) => write!(f, "{} was dealt more than once", card_name(*card)),
+            DealError::NotInDeck(card) => write!(f, "card {} is not one of the {} in the deck", card, DECK_SIZE),
+            DealError::NotEnoughCards { needed, remaining } => {
+                write!(f, "the deal needs {} cards but only {} are left in the deck", needed, remaining)
+            }
+        }
+    }
+}
+impl std::error::Error for DealError {}
+
+/// One deal: every seat's hand plus the dealer's candidate cards
+#[derive(Clone, Debug, PartialEq)]
+pub struct Round {
+    player_hands: [[usize; HAND_SIZE]; NUM_PLAYERS],
+    dealer_cards: [usize; DEALER_CARDS],
+}
+impl Round {
+    pub fn new(player_hands: [[usize; HAND_SIZE]; NUM_PLAYERS], dealer_cards: [usize; DEALER_CARDS]) -> Self {
+        Round { player_hands, dealer_cards }
+    }
+    /// The round a Deck::new_seeded() deck deals, so a seed names a deal that can be shown again
+    pub fn seeded(seed: u64) -> Self {
+        Deck::new_seeded(seed).deal_round()
+    }
+    /// The hand dealt to a seat
+    pub fn player(&self, seat: usize) -> &[usize; HAND_SIZE] {
+        &self.player_hands[seat]
+    }
+    pub fn player_hands(&self) -> &[[usize; HAND_SIZE]; NUM_PLAYERS] {
+        &self.player_hands
+    }
+    pub fn dealer_cards(&self) -> &[usize; DEALER_CARDS] {
+        &self.dealer_cards
+    }
+    /// dealer_cards() as the pool the dealer's hand is drawn from
+    pub fn dealer_pool(&self) -> DealerPool {
+        DealerPool(self.dealer_cards)
+    }
+    /// Checks that no card was dealt twice
+    pub fn validate(&self) -> Result<(), DealError> {
+        if let Some(&card) = self.player_hands.iter().flatten().chain(self.dealer_cards.iter()).find(|&&card| card >= DECK_SIZE) {
+            return Err(DealError::NotInDeck(card));
+        }
+        match repeated_card(self.player_hands.iter().flatten().chain(self.dealer_cards.iter())) {
+            Some(card) => Err(DealError::DuplicateCard(card)),
+            None => Ok(()),
+        }
+    }
+}
+/// First seed below max_tries whose Round::seeded() matches the predicate, for finding teaching
+/// examples to replay with the trace_deal example
+pub fn find_seed<F: Fn(&Round) -> bool>(predicate: F, max_tries: u64) -> Option<u64> {
+    (0..max_tries).find(|&seed| predicate(&Round::seeded(seed)))
+}
+
+impl std::fmt::Display for Round {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (seat, hand) in self.player_hands.iter().enumerate() {
+            writeln!(f, "Seat {}: {}", seat, arr_to_strings(hand).join(" "))?;
+        }
+        write!(f, "Dealer cards: {}", arr_to_strings(&self.dealer_cards).join(" "))
+    }
+}
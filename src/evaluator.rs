@@ -0,0 +1,755 @@
+//! Flush evaluation and hand settlement
+
+use rand_chacha::ChaCha8Rng;
+use rand::SeedableRng;
+use crate::card::{DEALER_CARDS, HAND_SIZE, NUM_PLAYERS, PlayerHand, RANKS, Rank, SUITS, Suit, WILD_CARD, add_suit_counts, repeated_card};
+use crate::deck::{DealError, Deck};
+use crate::rules::{FlushBonusPaytable, GameConfig, NonQualifyRule, QualifyMode, TieBreak};
+use crate::probability::{DEALER_COMBINATIONS, FlushCategoryCounts};
+
+/// Cards of one suit sorted by rank (high to low), derefs to a slice of those cards
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Flush {
+    cards: [usize; RANKS],
+    len: usize,
+}
+impl Flush {
+    pub(crate) fn from_cards(cards: &[usize]) -> Self {
+        let mut flush = Flush { cards: [0; RANKS], len: cards.len() };
+        flush.cards[..cards.len()].copy_from_slice(cards);
+        flush
+    }
+    /// The n highest cards of the flush, or the whole flush if it has fewer than n cards
+    pub fn top_n(&self, n: usize) -> &[usize] {
+        &self[..n.min(self.len)]
+    }
+}
+impl std::ops::Deref for Flush {
+    type Target = [usize];
+    fn deref(&self) -> &[usize] {
+        &self.cards[..self.len]
+    }
+}
+
+/// Returns the flush cards sorted by rank (high to low)
+/// Not marked #[inline]: with and without the hint on this and the mask helpers, --bench throughput
+/// (100k seeded deals, release, five runs each) took 1.89-2.00s and 1.95-1.98s, within noise, as
+/// rustc already inlines small functions within the crate. Only the 7-card instance is built, once
+/// here and once in the binary, so splitting out a non-generic body would save nothing
+pub fn get_best_flush<const N: usize>(hand: &[usize; N]) -> Flush {
+    let suits = flushes_by_suit(hand);
+    let best_flush = suits.iter()
+        .max_by(|a, b| compare_flush(a, b))
+        .cloned()
+        .unwrap_or(Vec::new());
+    Flush::from_cards(&best_flush)
+}
+
+/// How many cards of each rank the cards hold, indexed by rank (2 to A). Jokers are left out
+/// A flush counts 1 at each of its ranks, a whole hand can repeat ranks across suits
+pub fn rank_counts(cards: &[usize]) -> [u8; RANKS] {
+    let mut counts = [0; RANKS];
+    for &card in cards.iter().filter(|&&card| card != WILD_CARD) {
+        counts[Rank::of(card).to_index()] += 1;
+    }
+    counts
+}
+
+/// Every suit's cards in the hand sorted by rank (high to low), indexed by suit (d, c, h, s)
+/// Jokers belong to no suit and are left out
+pub fn flushes_by_suit(hand: &[usize]) -> [Vec<usize>; SUITS] {
+    let mut suits: [Vec<usize>; SUITS] = Default::default();
+    for &card in hand.iter() {
+        if let Some(suit) = Suit::of(card) {
+            suits[suit.to_index()].push(card);
+        }
+    }
+    for cards in suits.iter_mut() {
+        cards.sort_by(|&a, &b| Rank::of(b).cmp(&Rank::of(a)));
+    }
+    suits
+}
+
+/// Orders two flushes sorted high to low: longer wins, then the first differing rank
+pub fn compare_flush(a: &[usize], b: &[usize]) -> std::cmp::Ordering {
+    let len_cmp = a.len().cmp(&b.len());
+    if len_cmp != std::cmp::Ordering::Equal {
+        return len_cmp;
+    }
+    for i in 0..a.len().min(b.len()) {
+        let rank_cmp = Rank::of(a[i]).cmp(&Rank::of(b[i]));
+        if rank_cmp != std::cmp::Ordering::Equal {
+            return rank_cmp;
+        }
+    }
+    std::cmp::Ordering::Equal
+}
+
+/// Fixed-width sort key for a flush of up to 7 cards: the length, then the ranks high to low padded
+/// with zeros. Ord on keys matches compare_flush(), since keys only differ past the ranks when the
+/// lengths already differ
+pub fn flush_key(flush: &Flush) -> [u8; HAND_SIZE + 1] {
+    debug_assert!(flush.len() <= HAND_SIZE, "flush_key covers flushes of up to {} cards", HAND_SIZE);
+    let mut key = [0; HAND_SIZE + 1];
+    key[0] = flush.len() as u8;
+    for (i, &card) in flush.iter().take(HAND_SIZE).enumerate() {
+        key[i + 1] = Rank::of(card).to_index() as u8;
+    }
+    key
+}
+
+/// Compares two hands on flush strength alone, with no qualification or payouts
+pub fn player_vs_player<const N: usize>(hand_a: &[usize; N], hand_b: &[usize; N]) -> std::cmp::Ordering {
+    compare_flush(&get_best_flush(hand_a), &get_best_flush(hand_b))
+}
+
+// Bits of one suit's 13-card lane in a hand mask
+pub(crate) const SUIT_LANE: u64 = (1 << RANKS) - 1;
+
+/// Converts cards to a bitmask with bit `card` set for each card,
+/// so each suit occupies its own 13-bit lane with the ace as the lane's top bit
+pub fn hand_mask(cards: &[usize]) -> u64 {
+    cards.iter().fold(0, |mask, &card| mask | 1 << card)
+}
+
+/// get_best_flush() on a hand mask, counting suits with popcounts instead of a hashmap
+/// Within equal lengths a higher lane value is exactly a higher flush, since the top differing bit is the
+/// first differing rank
+pub fn get_best_flush_masked(mask: u64) -> Flush {
+    let (suit, lane) = best_lane(mask);
+    flush_from_lane(suit, lane)
+}
+
+/// Builds the flush for one suit's 13-bit lane
+pub(crate) fn flush_from_lane(suit: usize, lane: u64) -> Flush {
+    let mut flush = Flush { cards: [0; RANKS], len: 0 };
+    for rank in (0..RANKS).rev() {
+        if lane & (1 << rank) != 0 {
+            flush.cards[flush.len] = suit * RANKS + rank;
+            flush.len += 1;
+        }
+    }
+    flush
+}
+
+/// Readable hand dump grouped by suit, e.g. "♠: A K Q | ♦: 9 5 | ♣: 2". Longest suit first with ties
+/// in spades, hearts, clubs, diamonds order, ranks high to low, and any jokers listed last
+pub fn describe_hand(cards: &[usize]) -> String {
+    const SUIT_SYMBOLS: [&str; SUITS] = ["♦", "♣", "♥", "♠"];
+    let mask = hand_mask(cards);
+    let mut suits: Vec<(Suit, Flush)> = Suit::ALL
+        .into_iter()
+        .rev()
+        .map(|suit| (suit, flush_from_lane(suit.to_index(), (mask >> (suit.to_index() * RANKS)) & SUIT_LANE)))
+        .filter(|(_, flush)| !flush.is_empty())
+        .collect();
+    // Stable, so equal lengths keep the spades-first order
+    suits.sort_by_key(|(_, flush)| std::cmp::Reverse(flush.len()));
+    let mut groups: Vec<String> = suits
+        .iter()
+        .map(|(suit, flush)| {
+            let ranks: Vec<String> = flush.iter().map(|&card| Rank::of(card).to_string()).collect();
+            format!("{}: {}", SUIT_SYMBOLS[suit.to_index()], ranks.join(" "))
+        })
+        .collect();
+    let jokers = cards.iter().filter(|&&card| card == WILD_CARD).count();
+    if jokers > 0 {
+        groups.push(vec!["Jo"; jokers].join(" "));
+    }
+    groups.join(" | ")
+}
+
+/// get_best_flush() for the wild card variant, each WILD_CARD joins whichever suit it improves most
+/// as the highest rank that suit is missing, so the returned flush only holds real card integers
+pub(crate) fn get_best_flush_wild(cards: &[usize]) -> Flush {
+    let wilds = cards.iter().filter(|&&card| card == WILD_CARD).count();
+    let mask = cards.iter().filter(|&&card| card != WILD_CARD).fold(0u64, |mask, &card| mask | 1 << card);
+    let (suit, lane) = (0..SUITS)
+        .map(|suit| {
+            let mut lane = (mask >> (suit * RANKS)) & SUIT_LANE;
+            for _ in 0..wilds {
+                let missing = !lane & SUIT_LANE;
+                if missing != 0 {
+                    lane |= 1 << (63 - missing.leading_zeros());
+                }
+            }
+            (suit, lane)
+        })
+        .max_by_key(|&(_, lane)| (lane.count_ones(), lane))
+        .unwrap();
+    flush_from_lane(suit, lane)
+}
+
+/// Best flush length of each hand in a batch, using only fixed-size suit counters so the loop
+/// has no allocation or sorting and can be auto-vectorized
+pub fn best_flush_lengths(hands: &[[usize; HAND_SIZE]]) -> Vec<usize> {
+    hands.iter()
+        .map(|hand| {
+            let mut counts = [0; SUITS];
+            add_suit_counts(&mut counts, hand);
+            counts.into_iter().max().unwrap()
+        })
+        .collect()
+}
+
+/// Net units of a 1-unit Flush Bonus bet on the hand: the paytable's odds for its best flush length,
+/// 0 on a push, or -1 when that length loses
+pub fn flush_bonus_payout(hand: &[usize; HAND_SIZE], table: &FlushBonusPaytable) -> i32 {
+    table.odds[get_best_flush_masked(hand_mask(hand)).len()].unwrap_or(-1)
+}
+
+/// flush_bonus_payout() for the wild card variant, each WILD_CARD extending the flush it joins
+pub fn flush_bonus_payout_wild(hand: &[usize; HAND_SIZE], table: &FlushBonusPaytable) -> i32 {
+    table.odds[get_best_flush_wild(hand).len()].unwrap_or(-1)
+}
+
+/// Length of the longest run of consecutive ranks in a suited group such as get_best_flush() returns,
+/// in any order. The Ace plays high after the King or low before the 2, so A-2-3 is a 3-card run
+pub fn longest_straight_flush(flush: &[usize]) -> usize {
+    let ranks = flush.iter().fold(0u32, |ranks, &card| ranks | 1 << Rank::of(card).to_index());
+    // Shift the 2-A lane up one and put the Ace in the freed bottom bit as well
+    let mut lane = ranks << 1 | ranks >> (RANKS - 1);
+    let mut longest = 0;
+    while lane != 0 {
+        lane &= lane << 1;
+        longest += 1;
+    }
+    longest.min(flush.len())
+}
+
+/// Returns the length of the longest straight flush in a flush, see longest_straight_flush()
+/// Runs shorter than 3 cards are not straight flushes and return 0
+pub(crate) fn straight_flush_len(flush: &[usize]) -> usize {
+    let longest = longest_straight_flush(flush);
+    if longest >= 3 { longest } else { 0 }
+}
+
+/// Compares player and dealer hands, returns how each bet settled, see Outcome::net_antes()
+/// Player hand is ALWAYS the first parameter, dealer hand is the second
+/// Overlap is not checked: each hand is evaluated on its own flush structure, so a card in both
+/// hands counts for both and a card repeated within one hand counts once
+pub fn compare_hands<const P: usize, const D: usize>(
+    player_hand: &[usize; P], 
+    dealer_hand: &[usize; D]
+) -> Outcome {
+    compare_hands_detailed(player_hand, dealer_hand, &GameConfig::STANDARD).outcome()
+}
+
+/// compare_hands() that rejects cards outside the deck (a joker is allowed) and hands sharing or
+/// repeating a card
+pub fn compare_hands_checked<const P: usize, const D: usize>(
+    player_hand: &[usize; P],
+    dealer_hand: &[usize; D]
+) -> Result<Outcome, DealError> {
+    if let Some(&card) = player_hand.iter().chain(dealer_hand.iter()).find(|&&card| card > WILD_CARD) {
+        return Err(DealError::NotInDeck(card));
+    }
+    match repeated_card(player_hand.iter().chain(dealer_hand.iter())) {
+        Some(card) => Err(DealError::DuplicateCard(card)),
+        None => Ok(compare_hands(player_hand, dealer_hand)),
+    }
+}
+
+/// compare_hands() under the given table rules
+pub fn compare_hands_with<const P: usize, const D: usize>(
+    player_hand: &[usize; P],
+    dealer_hand: &[usize; D],
+    config: &GameConfig
+) -> i32 {
+    compare_hands_detailed(player_hand, dealer_hand, config).net()
+}
+
+/// What the play bet alone adds to compare_hands_with(): the full result minus ante_only_result()
+pub fn play_bet_marginal<const P: usize, const D: usize>(player_hand: &[usize; P], dealer_hand: &[usize; D], config: &GameConfig) -> i32 {
+    compare_hands_detailed(player_hand, dealer_hand, config).play
+}
+
+/// The result had the player only put up the ante (and any blind bonus), with no play bet
+pub fn ante_only_result<const P: usize, const D: usize>(player_hand: &[usize; P], dealer_hand: &[usize; D], config: &GameConfig) -> i32 {
+    let payout = compare_hands_detailed(player_hand, dealer_hand, config);
+    payout.ante + payout.bonus
+}
+
+/// Net result of a hand split by bet, all in antes, along with how the hand settled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PayoutBreakdown {
+    pub ante: i32,
+    pub play: i32,
+    pub bonus: i32,
+    pub class: HandClass,
+}
+impl PayoutBreakdown {
+    const PUSH: PayoutBreakdown = PayoutBreakdown { ante: 0, play: 0, bonus: 0, class: HandClass::Push };
+    /// The single number Outcome::net_antes() reports
+    pub fn net(&self) -> i32 {
+        self.ante + self.play + self.bonus
+    }
+    /// Win, loss, push or dealer not qualified, as settle_flushes() decided it
+    pub fn class(&self) -> HandClass {
+        self.class
+    }
+    /// The Outcome these bets add up to. A straight flush blind bonus is counted with the ante
+    pub fn outcome(&self) -> Outcome {
+        match self.class {
+            HandClass::Win => Outcome::PlayerWins { ante: self.ante + self.bonus, play: self.play },
+            HandClass::Loss => Outcome::DealerWins { ante: -self.ante, play: -self.play },
+            HandClass::Push => Outcome::Push,
+            HandClass::DealerNotQualified => Outcome::DealerNotQualified { ante: self.ante + self.bonus },
+        }
+    }
+}
+
+/// How a played hand settled against one dealer hand, with the antes each bet won for whichever side
+/// won it. The play bet pushes when the dealer doesn't qualify, so that case only carries the ante
+/// (which pays, or pushes under NonQualifyRule::Push)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    PlayerWins { ante: i32, play: i32 },
+    DealerWins { ante: i32, play: i32 },
+    Push,
+    DealerNotQualified { ante: i32 },
+}
+impl Outcome {
+    /// The player's net result in antes, e.g. 3 for a win with a 2x play bet or -2 for a loss with a
+    /// 1x play bet
+    pub fn net_antes(&self) -> i32 {
+        match *self {
+            Outcome::PlayerWins { ante, play } => ante + play,
+            Outcome::DealerWins { ante, play } => -(ante + play),
+            Outcome::Push => 0,
+            Outcome::DealerNotQualified { ante } => ante,
+        }
+    }
+}
+
+/// How a played hand turned out against one dealer hand
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HandClass {
+    Win,
+    Loss,
+    Push,
+    DealerNotQualified,
+}
+
+/// Classifies a player hand against a dealer hand
+pub fn classify_hands<const P: usize, const D: usize>(
+    player_hand: &[usize; P],
+    dealer_hand: &[usize; D],
+    config: &GameConfig
+) -> HandClass {
+    compare_hands_detailed(player_hand, dealer_hand, config).class()
+}
+
+/// Counts of each HandClass, one per dealer hand a played hand was compared against
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OutcomeCounts {
+    pub wins: u64,
+    pub losses: u64,
+    pub pushes: u64,
+    pub dealer_no_qualify: u64,
+}
+impl OutcomeCounts {
+    pub(crate) fn record(&mut self, class: HandClass) {
+        match class {
+            HandClass::Win => self.wins += 1,
+            HandClass::Loss => self.losses += 1,
+            HandClass::Push => self.pushes += 1,
+            HandClass::DealerNotQualified => self.dealer_no_qualify += 1,
+        }
+    }
+    pub(crate) fn add(&mut self, other: &OutcomeCounts) {
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.pushes += other.pushes;
+        self.dealer_no_qualify += other.dealer_no_qualify;
+    }
+    pub fn total(&self) -> u64 {
+        self.wins + self.losses + self.pushes + self.dealer_no_qualify
+    }
+    /// Share of compared hands the player won, including hands where the dealer didn't qualify
+    pub fn win_rate(&self) -> f64 {
+        (self.wins + self.dealer_no_qualify) as f64 / self.total() as f64
+    }
+}
+
+/// compare_hands_with() keeping the ante, play bet and bonus results apart
+/// The const generics only size the arrays handed on to hand_mask() and kicker_ranks(), and every
+/// caller in the crate compares 7 cards with 7, so the release build holds a single instance of
+/// this and of settle_flushes() (checked with nm) and there is no duplicate code to split out
+pub fn compare_hands_detailed<const P: usize, const D: usize>(
+    player_hand: &[usize; P],
+    dealer_hand: &[usize; D],
+    config: &GameConfig
+) -> PayoutBreakdown {
+    if !config.wild_cards && config.dealer_plays.is_none() {
+        if let Some(payout) = settle_lanes(best_lane(hand_mask(player_hand)), best_lane(hand_mask(dealer_hand)), config) {
+            return payout;
+        }
+    }
+    compare_hands_full(player_hand, dealer_hand, config)
+}
+
+/// compare_hands_detailed() without the fast path, building both flushes and walking their ranks.
+/// The reference settle_lanes() must agree with
+pub(crate) fn compare_hands_full<const P: usize, const D: usize>(
+    player_hand: &[usize; P],
+    dealer_hand: &[usize; D],
+    config: &GameConfig
+) -> PayoutBreakdown {
+    let player_flush = hand_flush(player_hand, config);
+    let dealer_flush = hand_flush(dealer_hand, config);
+    settle_flushes(player_hand, &player_flush, dealer_hand, &dealer_flush, config)
+}
+
+/// Suit and 13-bit lane of a hand mask's best flush, as get_best_flush_masked() picks it
+fn best_lane(mask: u64) -> (usize, u64) {
+    (0..SUITS)
+        .map(|suit| (suit, (mask >> (suit * RANKS)) & SUIT_LANE))
+        .max_by_key(|&(_, lane)| (lane.count_ones(), lane))
+        .unwrap()
+}
+
+/// Fast path of compare_hands_detailed() on the best suit lanes alone, for rules without jokers or a
+/// dealer who folds. A dealer who doesn't qualify, a longer flush, or a higher lane (the top differing
+/// bit is the first differing rank) settles the hand without building either flush or walking ranks.
+/// None when the lanes are equal and the table's tie rule has to decide
+fn settle_lanes((player_suit, player_lane): (usize, u64), (_, dealer_lane): (usize, u64), config: &GameConfig) -> Option<PayoutBreakdown> {
+    let dealer_len = dealer_lane.count_ones() as usize;
+    let dealer_top = (dealer_lane != 0).then(|| 63 - dealer_lane.leading_zeros() as usize);
+    let qualifies = config.qualify_mode == QualifyMode::AlwaysQualify
+        || dealer_len > config.qualify_len
+        || (dealer_len == config.qualify_len && dealer_top.is_some_and(|top| top >= config.qualify_rank));
+    if !qualifies {
+        return Some(dealer_out_payout(config));
+    }
+    let player_len = player_lane.count_ones() as usize;
+    let play_bet_multiplier = config.play_multiplier(player_len);
+    match (player_len, player_lane).cmp(&(dealer_len, dealer_lane)) {
+        std::cmp::Ordering::Greater => {
+            let bonus = match config.straight_flush_blind {
+                Some(_) => blind_bonus(&flush_from_lane(player_suit, player_lane), config),
+                None => 0,
+            };
+            Some(PayoutBreakdown { ante: 1, play: play_bet_multiplier, bonus, class: HandClass::Win })
+        }
+        std::cmp::Ordering::Less => Some(PayoutBreakdown { ante: -1, play: -play_bet_multiplier, bonus: 0, class: HandClass::Loss }),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Best flush of a hand under the table rules
+fn hand_flush<const N: usize>(hand: &[usize; N], config: &GameConfig) -> Flush {
+    if config.wild_cards {
+        get_best_flush_wild(hand)
+    } else {
+        get_best_flush_masked(hand_mask(hand))
+    }
+}
+
+/// compare_hands_detailed() with both best flushes already found, so callers settling one hand
+/// against many can find each flush once
+fn settle_flushes<const P: usize, const D: usize>(
+    player_hand: &[usize; P],
+    player_flush: &Flush,
+    dealer_hand: &[usize; D],
+    dealer_flush: &Flush,
+    config: &GameConfig
+) -> PayoutBreakdown {
+    if dealer_sits_out(dealer_flush, config) {
+        return dealer_out_payout(config);
+    }
+    let play_bet_multiplier = config.play_multiplier(player_flush.len());
+    let win = PayoutBreakdown { ante: 1, play: play_bet_multiplier, bonus: blind_bonus(player_flush, config), class: HandClass::Win };
+    let loss = PayoutBreakdown { ante: -1, play: -play_bet_multiplier, bonus: 0, class: HandClass::Loss };
+    if player_flush.len() > dealer_flush.len() {
+        return win;
+    } else if player_flush.len() < dealer_flush.len() {
+        return loss;
+    }
+    for i in 0..player_flush.len().min(dealer_flush.len()) {
+        let player_rank = Rank::of(player_flush[i]);
+        let dealer_rank = Rank::of(dealer_flush[i]);
+
+        if player_rank > dealer_rank {
+            return win;
+        } else if player_rank < dealer_rank {
+            return loss;
+        }
+    }
+    let tie = match config.tie_break {
+        TieBreak::Push => std::cmp::Ordering::Equal,
+        TieBreak::SuitRank => flush_suit(player_flush).cmp(&flush_suit(dealer_flush)),
+        TieBreak::HighestKicker => kicker_ranks(player_hand, player_flush).cmp(&kicker_ranks(dealer_hand, dealer_flush)),
+    };
+    match tie {
+        std::cmp::Ordering::Greater => win,
+        std::cmp::Ordering::Less => loss,
+        std::cmp::Ordering::Equal => PayoutBreakdown::PUSH,
+    }
+}
+
+/// Straight flush blind bonus for the player's flush, only paid when the player beats a dealer who
+/// stays in, never when the dealer sits out
+fn blind_bonus(player_flush: &Flush, config: &GameConfig) -> i32 {
+    match config.straight_flush_blind {
+        // Hands longer than 7 cards can hold a longer straight flush, which pays the top entry
+        Some(paytable) => paytable[straight_flush_len(player_flush).min(paytable.len() - 1)],
+        None => 0,
+    }
+}
+
+/// True when the dealer doesn't qualify or folds, which settles a played hand without comparing flushes
+fn dealer_sits_out(dealer_flush: &Flush, config: &GameConfig) -> bool {
+    config.dealer_plays.is_some_and(|dealer_plays| !dealer_plays(dealer_flush)) || !dealer_qualifies(dealer_flush, config)
+}
+
+/// Result of a played hand when the dealer sits out: the play bet pushes, the ante pays or pushes by the
+/// rule, and no blind bonus is paid since the player's flush never won a showdown
+fn dealer_out_payout(config: &GameConfig) -> PayoutBreakdown {
+    let ante = match config.non_qualify {
+        NonQualifyRule::PayAnte => 1,
+        NonQualifyRule::Push => 0,
+    };
+    PayoutBreakdown { ante, play: 0, bonus: 0, class: HandClass::DealerNotQualified }
+}
+
+/// Whether a dealer flush qualifies: longer than the qualify length, or exactly that long and topped
+/// by the qualify rank or better (4 cards, or 3 from 9-high, in the standard game). An empty flush
+/// has no top card, so it only qualifies under AlwaysQualify
+pub(crate) fn dealer_qualifies(dealer_flush: &Flush, config: &GameConfig) -> bool {
+    config.qualify_mode == QualifyMode::AlwaysQualify
+        || dealer_flush.len() > config.qualify_len
+        || (dealer_flush.len() == config.qualify_len
+            && dealer_flush.first().zip(Rank::from_index(config.qualify_rank)).is_some_and(|(&top, min_rank)| Rank::of(top) >= min_rank))
+}
+
+/// Every dealer hand from the candidate cards whose best flush qualifies under the standard rules
+pub fn qualifying_dealer_hands(dealer_cards: &[usize; DEALER_CARDS]) -> Vec<[usize; HAND_SIZE]> {
+    let mut hands = Vec::new();
+    for_each_dealer_hand(dealer_cards, &mut |dealer_hand| {
+        if dealer_qualifies(&get_best_flush_masked(hand_mask(dealer_hand)), &GameConfig::STANDARD) {
+            hands.push(*dealer_hand);
+        }
+    });
+    hands
+}
+
+/// Counts of how the dealer's best flush fared against the qualifying rule: longer than the
+/// qualifying length, at that length and high enough (any qualifying hand under AlwaysQualify
+/// that isn't longer lands here too), or not qualifying, along with the flush category of every hand
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DealerQualifyStats {
+    pub longer: u64,
+    pub at_length: u64,
+    pub not_qualified: u64,
+    pub categories: FlushCategoryCounts,
+}
+impl DealerQualifyStats {
+    pub fn record(&mut self, dealer_flush: &Flush, config: &GameConfig) {
+        self.categories.record(dealer_flush.len());
+        if !dealer_qualifies(dealer_flush, config) {
+            self.not_qualified += 1;
+        } else if dealer_flush.len() > config.qualify_len {
+            self.longer += 1;
+        } else {
+            self.at_length += 1;
+        }
+    }
+    pub fn total(&self) -> u64 {
+        self.longer + self.at_length + self.not_qualified
+    }
+}
+
+/// DealerQualifyStats over seeded deals, taking the dealer's hand as the first 7 candidate cards
+/// as simulate_direct() does
+pub fn dealer_qualify_stats(config: &GameConfig, iterations: usize, seed: u64) -> DealerQualifyStats {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_cards = [0; DEALER_CARDS];
+    let mut dealer = [0; HAND_SIZE];
+    let mut stats = DealerQualifyStats::default();
+    for _ in 0..iterations {
+        deck.shuffle_with(&mut rng);
+        deck.deal_into(&mut hands, &mut dealer_cards);
+        dealer.copy_from_slice(&dealer_cards[..HAND_SIZE]);
+        stats.record(&hand_flush(&dealer, config), config);
+    }
+    stats
+}
+
+/// Dealer rule for GameConfig::dealer_plays that only stays in with a 4-card flush or better
+pub fn dealer_plays_four_flush(flush: &Flush) -> bool {
+    flush.len() >= 4
+}
+
+/// Suit of a flush, ignoring a joker standing in for one of its cards
+pub(crate) fn flush_suit(flush: &Flush) -> Option<Suit> {
+    flush.iter().find_map(|&card| Suit::of(card))
+}
+
+/// Ranks of the cards outside the flush, high to low, for TieBreak::HighestKicker
+fn kicker_ranks<const N: usize>(hand: &[usize; N], flush: &Flush) -> Vec<Rank> {
+    let mut ranks: Vec<Rank> = hand
+        .iter()
+        .filter(|&card| *card != WILD_CARD && !flush.contains(card))
+        .map(|&card| Rank::of(card))
+        .collect();
+    ranks.sort_unstable_by(|a, b| b.cmp(a));
+    ranks
+}
+
+/// Calculates the average wager result across all possible dealer hands given the 10 remaining dealer cards and the player's 7 cards
+/// Player hand is ALWAYS the first parameter, dealer cards is the second
+pub fn calculate_average_result(
+    player_cards: &[usize; HAND_SIZE], 
+    dealer_cards: &[usize; DEALER_CARDS]
+) -> f64 {
+    calculate_average_result_with(player_cards, dealer_cards, &GameConfig::STANDARD)
+}
+
+/// calculate_average_result() under the given table rules
+pub fn calculate_average_result_with(
+    player_cards: &[usize; HAND_SIZE],
+    dealer_cards: &[usize; DEALER_CARDS],
+    config: &GameConfig
+) -> f64 {
+    tally_average_result(player_cards, dealer_cards, config).0
+}
+
+/// calculate_average_result_with() that also counts how each dealer hand turned out
+pub(crate) fn tally_average_result(
+    player_cards: &[usize; HAND_SIZE],
+    dealer_cards: &[usize; DEALER_CARDS],
+    config: &GameConfig
+) -> (f64, OutcomeCounts) {
+    let mut total_result = 0;
+    let mut counts = OutcomeCounts::default();
+    let player_flush = hand_flush(player_cards, config);
+    for_each_dealer_hand(dealer_cards, &mut |dealer_hand| {
+        let payout = settle_flushes(player_cards, &player_flush, dealer_hand, &hand_flush(dealer_hand, config), config);
+        total_result += payout.outcome().net_antes();
+        counts.record(payout.class());
+    });
+    debug_assert_eq!(counts.total(), DEALER_COMBINATIONS);
+    (total_result as f64 / counts.total() as f64, counts)
+}
+
+/// calculate_average_result() for every seat at once. Each of the C(10, 7) dealer hands is
+/// enumerated and its flush found once, then settled against all six player flushes
+pub fn table_results(hands: &[[usize; HAND_SIZE]; NUM_PLAYERS], dealer_cards: &[usize; DEALER_CARDS]) -> [f64; NUM_PLAYERS] {
+    table_results_with(hands, dealer_cards, &GameConfig::STANDARD)
+}
+
+/// table_results() under the given table rules
+pub fn table_results_with(
+    hands: &[[usize; HAND_SIZE]; NUM_PLAYERS],
+    dealer_cards: &[usize; DEALER_CARDS],
+    config: &GameConfig
+) -> [f64; NUM_PLAYERS] {
+    let player_flushes = hands.map(|hand| hand_flush(&hand, config));
+    let mut totals = [0; NUM_PLAYERS];
+    // A dealer hand that sits out settles every seat the same way whatever either flush, so those hands
+    // are only counted and their result is added once at the end
+    let mut dealer_out_hands = 0;
+    for_each_dealer_hand(dealer_cards, &mut |dealer_hand| {
+        let dealer_flush = hand_flush(dealer_hand, config);
+        if dealer_sits_out(&dealer_flush, config) {
+            dealer_out_hands += 1;
+            return;
+        }
+        for seat in 0..NUM_PLAYERS {
+            totals[seat] += settle_flushes(&hands[seat], &player_flushes[seat], dealer_hand, &dealer_flush, config).net();
+        }
+    });
+    for total in totals.iter_mut() {
+        *total += dealer_out_hands * dealer_out_payout(config).net();
+    }
+    totals.map(|total| total as f64 / DEALER_COMBINATIONS as f64)
+}
+
+/// Calls visit with each 7-card dealer hand drawn from the candidate cards
+pub(crate) fn for_each_dealer_hand<F: FnMut(&[usize; HAND_SIZE])>(dealer_cards: &[usize; DEALER_CARDS], visit: &mut F) {
+    visit_combinations(dealer_cards, 0, &mut [0; HAND_SIZE], 0, visit);
+}
+
+/// Helper function for for_each_dealer_hand()
+pub(crate) fn visit_combinations<const N: usize, F: FnMut(&[usize; HAND_SIZE])>(
+    arr: &[usize; N],
+    start: usize,
+    current: &mut [usize; HAND_SIZE],
+    depth: usize,
+    visit: &mut F
+) {
+    if depth == HAND_SIZE {
+        visit(current);
+        return;
+    }
+    for i in start..N {
+        current[depth] = arr[i];
+        visit_combinations(arr, i + 1, current, depth + 1, visit);
+    }
+}
+
+/// The dealer's 10 candidate cards. The dealer's real hand is only some 7 of them, so this is
+/// what calculate_average_result() averages over, while compare_hands() takes a DealerHand
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DealerPool(pub [usize; DEALER_CARDS]);
+impl DealerPool {
+    /// Every one of the C(10, 7) hands the dealer could hold
+    pub fn combinations(&self) -> Vec<DealerHand> {
+        let mut hands = Vec::with_capacity(DEALER_COMBINATIONS as usize);
+        for_each_dealer_hand(&self.0, &mut |hand| hands.push(DealerHand(*hand)));
+        hands
+    }
+    /// The player's result averaged over combinations()
+    pub fn average_result(&self, player: &PlayerHand) -> f64 {
+        calculate_average_result(&player.0, &self.0)
+    }
+}
+
+/// The 7 cards the dealer actually holds
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DealerHand(pub [usize; HAND_SIZE]);
+impl DealerHand {
+    /// The player's result against this hand in antes
+    pub fn result(&self, player: &PlayerHand) -> i32 {
+        compare_hands(&player.0, &self.0).net_antes()
+    }
+}
+
+/// A seat's choice for one hand, Play holds the play bet in antes (1-3, set by flush length)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Decision {
+    Fold,
+    Play(u8),
+}
+impl Decision {
+    /// Converts a raise multiplier where 0 means fold
+    pub fn from_multiplier(multiplier: u8) -> Decision {
+        match multiplier {
+            0 => Decision::Fold,
+            m => Decision::Play(m),
+        }
+    }
+}
+
+/// EV-maximizing decision for a hand that knows the dealer's candidate cards, the ceiling any
+/// strategy can reach. The play bet is fixed by flush length, so this plays exactly when the
+/// average result beats the ante lost by folding
+pub fn optimal_decision(hand: &[usize; HAND_SIZE], dealer_cards: &[usize; DEALER_CARDS]) -> Decision {
+    let config = GameConfig::STANDARD;
+    if calculate_average_result(hand, dealer_cards) > -config.fold_cost {
+        Decision::Play(config.play_multiplier(get_best_flush_masked(hand_mask(hand)).len()) as u8)
+    } else {
+        Decision::Fold
+    }
+}
+
+/// Antes put at risk by a decision: the ante, plus the play bet when the hand is played
+pub fn units_wagered(decision: Decision) -> f64 {
+    match decision {
+        Decision::Fold => 1.0,
+        Decision::Play(multiplier) => 1.0 + multiplier as f64,
+    }
+}
@@ -0,0 +1,56 @@
+//! Regression fixtures of known hands
+
+use crate::card::{HAND_SIZE, arr_to_strings, hand_from_strs};
+use crate::evaluator::compare_hands_checked;
+
+/// One checked row of a fixture file
+pub struct FixtureResult {
+    pub row: usize,
+    pub player: [usize; HAND_SIZE],
+    pub dealer: [usize; HAND_SIZE],
+    pub expected: i32,
+    pub actual: i32,
+}
+impl FixtureResult {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+impl std::fmt::Display for FixtureResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f, "row {}: player {} vs dealer {} expected {}, got {}",
+            self.row, arr_to_strings(&self.player).join(" "), arr_to_strings(&self.dealer).join(" "),
+            self.expected, self.actual
+        )
+    }
+}
+
+/// Runs compare_hands() on every row of a CSV with columns player_cards,dealer_cards,expected_result,
+/// where the hands are space separated card strings like "As Ks Qs Js Ts 2d 3c". An error names the
+/// first row that can't be read or whose hands share a card, since no real deal could produce it
+pub fn run_fixture<P: AsRef<std::path::Path>>(path: P) -> Result<Vec<FixtureResult>, Box<dyn std::error::Error>> {
+    run_fixture_reader(std::fs::File::open(path)?)
+}
+
+/// run_fixture() on CSV text from any reader
+pub(crate) fn run_fixture_reader<R: std::io::Read>(reader: R) -> Result<Vec<FixtureResult>, Box<dyn std::error::Error>> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let mut results = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        // Row 1 is the header
+        let row = i + 2;
+        let field = |column: usize| record.get(column).ok_or(format!("row {}: missing column {}", row, column + 1));
+        let hand = |column: usize| -> Result<[usize; HAND_SIZE], String> {
+            let strs: Vec<&str> = field(column)?.split_whitespace().collect();
+            hand_from_strs(&strs).map_err(|e| format!("row {}: {}", row, e))
+        };
+        let player = hand(0)?;
+        let dealer = hand(1)?;
+        let expected = field(2)?.trim().parse().map_err(|e| format!("row {}: {}", row, e))?;
+        let actual = compare_hands_checked(&player, &dealer).map_err(|e| format!("row {}: {}", row, e))?.net_antes();
+        results.push(FixtureResult { row, player, dealer, expected, actual });
+    }
+    Ok(results)
+}
@@ -0,0 +1,41 @@
+//! Cards are represented as integers from 0-51
+//! Diamonds: 0-12, Clubs: 13-25, Hearts: 26-38, Spades: 39-51
+//! 2-Ace in each suit: 0/13/26/39 = 2, 12/25/38/51 = Ace
+//!
+//! Units: every result is in antes (1.0 = one ante). A hand is one seat's 7 cards for one deal, so
+//! "per hand" averages divide by seats dealt, while "per unit wagered" divides by antes put at risk
+//! (the ante plus any play bet, see units_wagered())
+
+// Rounds dealt, seat decisions and simulation summaries go through the log crate when built with the
+// logging feature, so embedders can route them; without it the macros below expand to nothing
+#[cfg(feature = "logging")]
+#[macro_use]
+extern crate log;
+#[cfg(not(feature = "logging"))]
+macro_rules! debug {
+    ($($arg:tt)*) => {};
+}
+#[cfg(not(feature = "logging"))]
+macro_rules! info {
+    ($($arg:tt)*) => {};
+}
+
+pub mod card;
+pub mod deck;
+pub mod rules;
+pub mod evaluator;
+pub mod probability;
+pub mod strategy;
+pub mod simulation;
+pub mod analysis;
+pub mod fixture;
+pub mod report;
+pub mod session;
+pub mod bench;
+pub mod progressive;
+#[cfg(feature = "async")]
+pub mod streaming;
+pub mod live;
+
+#[cfg(test)]
+mod tests;
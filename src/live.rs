@@ -0,0 +1,73 @@
+//! Advice for a live table from the cards seen so far
+
+use crate::card::{DECK_SIZE, HAND_SIZE, RANKS, SUITS};
+use crate::deck::DealError;
+use crate::rules::{GameConfig, QualifyMode};
+use crate::evaluator::SUIT_LANE;
+use crate::probability::n_choose_k;
+
+/// Cards not yet seen during a live hand, for advice as cards are exposed. Starts as the full deck
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LiveDeck {
+    // Bit per card observed so far
+    seen: u64,
+}
+impl LiveDeck {
+    pub fn new() -> Self {
+        LiveDeck::default()
+    }
+    /// Takes an exposed card out of the deck, an error if it isn't a card or was already seen
+    pub fn observe(&mut self, card: usize) -> Result<(), DealError> {
+        if card >= DECK_SIZE {
+            return Err(DealError::NotInDeck(card));
+        }
+        if self.seen & 1 << card != 0 {
+            return Err(DealError::DuplicateCard(card));
+        }
+        self.seen |= 1 << card;
+        Ok(())
+    }
+    pub fn remaining(&self) -> usize {
+        DECK_SIZE - self.seen.count_ones() as usize
+    }
+    /// Unseen cards of each suit (d, c, h, s)
+    pub fn suit_counts(&self) -> [usize; SUITS] {
+        std::array::from_fn(|suit| RANKS - ((self.seen >> (suit * RANKS)) & SUIT_LANE).count_ones() as usize)
+    }
+    /// Exact chance that a dealer hand of 7 cards drawn from the unseen ones qualifies under
+    /// config. Counts hands by how many cards they take from each suit, like
+    /// enumerate_flush_categories(), so nothing is sampled
+    pub fn qualify_probability(&self, config: &GameConfig) -> f64 {
+        assert!(self.remaining() >= HAND_SIZE, "a dealer hand needs {} unseen cards, {} left", HAND_SIZE, self.remaining());
+        if config.qualify_mode == QualifyMode::AlwaysQualify {
+            return 1.0;
+        }
+        let unseen = self.suit_counts();
+        // Unseen cards of each suit ranked below the qualify rank, the only ones a flush at exactly
+        // the qualifying length can hold and still miss
+        let low: [usize; SUITS] = std::array::from_fn(|suit| {
+            let lane = (self.seen >> (suit * RANKS)) & SUIT_LANE & ((1 << config.qualify_rank) - 1);
+            config.qualify_rank - lane.count_ones() as usize
+        });
+        let ways = |available: &[usize; SUITS], split: &[usize; SUITS]| -> u64 {
+            (0..SUITS).map(|suit| n_choose_k(available[suit] as u64, split[suit] as u64)).product()
+        };
+        let mut missed = 0;
+        for a in 0..=HAND_SIZE {
+            for b in 0..=HAND_SIZE - a {
+                for c in 0..=HAND_SIZE - a - b {
+                    let split = [a, b, c, HAND_SIZE - a - b - c];
+                    let longest = *split.iter().max().unwrap();
+                    if longest < config.qualify_len {
+                        missed += ways(&unseen, &split);
+                    } else if longest == config.qualify_len {
+                        // Every suit at the qualifying length has to top out below the qualify rank
+                        let limits = std::array::from_fn(|suit| if split[suit] == longest { low[suit] } else { unseen[suit] });
+                        missed += ways(&limits, &split);
+                    }
+                }
+            }
+        }
+        1.0 - missed as f64 / n_choose_k(self.remaining() as u64, HAND_SIZE as u64) as f64
+    }
+}
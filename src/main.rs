@@ -0,0 +1,626 @@
+use std::error::Error;
+use std::process::ExitCode;
+use std::str::FromStr;
+use rand_chacha::ChaCha8Rng;
+use rand::SeedableRng;
+use hcf::card::{NUM_PLAYERS, PlayerHand, RANKS, Rank, card_name, parse_card, vec_to_strings};
+use hcf::deck::{Round, find_seed};
+use hcf::rules::{FlushBonusPaytable, GameConfig};
+use hcf::evaluator::{dealer_qualify_stats, describe_hand, get_best_flush, get_best_flush_masked, hand_mask};
+use hcf::probability::{FlushCategory, flush_category_probabilities};
+use hcf::strategy::{Jacobson, Mousseau, Strategy, TableStrategy, mousseau_strategy, named_strategies};
+use hcf::simulation::{ap_heat, clairvoyant_edge, collusion_by_player_count, combine_seats, perfect_collusion_sim, perfect_collusion_sim_seeded, qualify_sensitivity, repeated_simulation, simulate_direct, simulate_mixed_table, simulate_mousseau_strategy, simulate_strategy_seeded, simulate_strategy_with, simulate_until};
+use hcf::analysis::{conditional_ev, direct_ev, paired_significance, result_accumulator, validate_pool_method};
+use hcf::fixture::{FixtureResult, run_fixture};
+use hcf::report::{OutputFormat, RunReport};
+use hcf::session::{StopRules, session_exits};
+use hcf::bench::{bench_dealing, bench_flush_lengths, bench_throughput};
+use hcf::live::LiveDeck;
+
+const USAGE: &str = "\
+usage: hcf [--seed S] [--quiet | --verbosity {silent,line,verbose}] [MODE]
+
+Without a mode, runs the perfect collusion, Mousseau and Jacobson baselines.
+Modes (the first one given runs):
+    --bench {dealing,flush,throughput}  time the hot-loop building blocks
+    --fixture PATH                      check compare_hands against a CSV of known results
+    --table PATH                        play a decision table from a CSV against built-in Jacobson
+    --output-format {human,json,csv}    one line per strategy in that format
+    --until PRECISION                   simulate until each edge is known to ±PRECISION antes
+    --mixed K                           seat K Jacobson colluders with Mousseau players
+    --find-seed                         first seed dealing a seat a 6+ card flush
+    --significance                      test each strategy against Mousseau on the same deals
+    --collusion-sweep                   Jacobson edge for 2 up to 6 colluding seats
+    --direct                            candidate-pool average against real dealer hands
+    --flush-stats                       flush category odds and dealer qualification rates
+    --validate-pool N                   candidate-pool average minus direct sampling over N deals
+    --flat-raise                        price the raise option against a 1x play bet
+    --max-raise N                       cap the play bet at N antes
+    --flush-bonus \"4:1 5:10 6:100 7:300\" add a Flush Bonus side bet at those odds (0 pushes)
+    --qualify-sensitivity               house edge at each 3-card qualification cutoff
+    --conditional \"Ks 9s 4s\"            average result of any hand holding those cards
+    --live \"As Kd 7h\"                   what is left in a live deck after those cards
+    --player-hand \"As Ks Qs Js Ts 2d 3c\" one hand against random dealer hands
+    --session BANKROLL [--bet B] [--stop-win W] [--stop-loss L]  bust rate of a bankroll betting B (default 1) a hand over 500 hands
+    --repeat K                          average K independently seeded runs";
+
+/// How much a run prints
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Verbosity {
+    // Nothing, only the exit code
+    Silent,
+    // One line of key=value pairs per result, for scripts
+    OneLine,
+    // Multi-line summaries
+    Verbose,
+}
+
+/// Prints one result in the form the verbosity asks for
+fn print_summary<V: Fn() -> String, L: Fn() -> String>(verbosity: Verbosity, verbose: V, one_line: L) {
+    match verbosity {
+        Verbosity::Silent => {}
+        Verbosity::OneLine => println!("{}", one_line()),
+        Verbosity::Verbose => println!("{}", verbose()),
+    }
+}
+
+/// What one run of the binary does, read from the first mode flag on the command line
+enum Command {
+    Bench(String),
+    Fixture(String),
+    Table(String),
+    OutputFormat(OutputFormat),
+    Until(f64),
+    Mixed(usize),
+    FindSeed,
+    Significance,
+    CollusionSweep,
+    Direct,
+    FlushStats,
+    ValidatePool(usize),
+    FlatRaise,
+    MaxRaise(i32, GameConfig),
+    FlushBonus(FlushBonusPaytable),
+    QualifySensitivity,
+    Conditional(String, Vec<usize>),
+    Live(Vec<usize>),
+    PlayerHand(PlayerHand),
+    Session(f64, f64, StopRules),
+    Repeat(usize),
+    Baselines,
+}
+
+/// A parsed command line. seed is None when --seed was not given, so the baselines can fall back to
+/// an unseeded collusion run; every other command uses base_seed()
+struct Invocation {
+    command: Command,
+    seed: Option<u64>,
+    verbosity: Verbosity,
+}
+impl Invocation {
+    fn base_seed(&self) -> u64 {
+        self.seed.unwrap_or(0)
+    }
+}
+
+/// Reads the text following a command line flag, e.g. --bench dealing
+fn flag_str<'a>(args: &'a [String], flag: &str) -> Result<Option<&'a str>, String> {
+    match args.iter().position(|arg| arg == flag) {
+        None => Ok(None),
+        Some(index) => args.get(index + 1).map(|value| Some(value.as_str())).ok_or(format!("{} expects a value", flag)),
+    }
+}
+
+/// Reads the number following a command line flag, e.g. --repeat 10
+fn flag_value<T: FromStr>(args: &[String], flag: &str) -> Result<Option<T>, String> {
+    match flag_str(args, flag)? {
+        None => Ok(None),
+        Some(value) => value.parse().map(Some).map_err(|_| format!("{} expects a number, got {}", flag, value)),
+    }
+}
+
+fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter().any(|arg| arg == flag)
+}
+
+/// Parses the cards of a flag like --live "As Kd 7h"
+fn parse_cards(flag: &str, text: &str) -> Result<Vec<usize>, String> {
+    text.split_whitespace()
+        .map(parse_card)
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|e| format!("could not parse {}: {}", flag, e))
+}
+
+// Flags followed by a value, which is skipped when checking for unknown arguments
+const VALUE_FLAGS: [&str; 19] = [
+    "--seed", "--verbosity", "--bench", "--fixture", "--table", "--output-format", "--until", "--mixed",
+    "--validate-pool", "--max-raise", "--flush-bonus", "--conditional", "--live", "--player-hand",
+    "--session", "--bet", "--stop-win", "--stop-loss", "--repeat",
+];
+// Flags that stand alone
+const SWITCH_FLAGS: [&str; 10] = [
+    "--quiet", "--find-seed", "--significance", "--collusion-sweep", "--direct", "--flush-stats",
+    "--flat-raise", "--qualify-sensitivity", "--help", "-h",
+];
+
+/// The first argument that is neither a known flag nor the value of one
+fn unknown_argument(args: &[String]) -> Option<&str> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if VALUE_FLAGS.contains(&arg.as_str()) {
+            args.next();
+        } else if !SWITCH_FLAGS.contains(&arg.as_str()) {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+/// Reads the command line (without the program name) into an Invocation, or explains what is wrong
+/// with it. Every argument must be a known flag or its value. Modes are checked in a fixed order and
+/// the first one present wins
+fn parse_args(args: &[String]) -> Result<Invocation, String> {
+    if let Some(unknown) = unknown_argument(args) {
+        return Err(format!("unknown option {}", unknown));
+    }
+    // --quiet prints nothing, --verbosity {silent,line,verbose} picks the summary format
+    let verbosity = match flag_str(args, "--verbosity")? {
+        _ if has_flag(args, "--quiet") => Verbosity::Silent,
+        Some("silent") => Verbosity::Silent,
+        Some("line") => Verbosity::OneLine,
+        Some("verbose") | None => Verbosity::Verbose,
+        Some(other) => return Err(format!("unknown verbosity {}, expected silent, line or verbose", other)),
+    };
+    let seed = flag_value(args, "--seed")?;
+
+    let command = if let Some(bench) = flag_str(args, "--bench")? {
+        match bench {
+            "dealing" | "flush" | "throughput" => Command::Bench(bench.to_string()),
+            _ => return Err(format!("unknown benchmark {}, expected dealing, flush or throughput", bench)),
+        }
+    } else if let Some(path) = flag_str(args, "--fixture")? {
+        Command::Fixture(path.to_string())
+    } else if let Some(path) = flag_str(args, "--table")? {
+        Command::Table(path.to_string())
+    } else if let Some(format) = flag_str(args, "--output-format")? {
+        Command::OutputFormat(match format {
+            "human" => OutputFormat::Human,
+            "json" => OutputFormat::Json,
+            "csv" => OutputFormat::Csv,
+            other => return Err(format!("unknown output format {}, expected human, json or csv", other)),
+        })
+    } else if let Some(precision) = flag_value(args, "--until")? {
+        Command::Until(precision)
+    } else if let Some(colluders) = flag_value(args, "--mixed")? {
+        if colluders > NUM_PLAYERS {
+            return Err(format!("--mixed expects at most {} colluders, got {}", NUM_PLAYERS, colluders));
+        }
+        Command::Mixed(colluders)
+    } else if has_flag(args, "--find-seed") {
+        Command::FindSeed
+    } else if has_flag(args, "--significance") {
+        Command::Significance
+    } else if has_flag(args, "--collusion-sweep") {
+        Command::CollusionSweep
+    } else if has_flag(args, "--direct") {
+        Command::Direct
+    } else if has_flag(args, "--flush-stats") {
+        Command::FlushStats
+    } else if let Some(trials) = flag_value(args, "--validate-pool")? {
+        Command::ValidatePool(trials)
+    } else if has_flag(args, "--flat-raise") {
+        Command::FlatRaise
+    } else if let Some(cap) = flag_value(args, "--max-raise")? {
+        Command::MaxRaise(cap, GameConfig::STANDARD.capped_raise(cap).map_err(|e| format!("invalid --max-raise: {}", e))?)
+    } else if let Some(text) = flag_str(args, "--flush-bonus")? {
+        Command::FlushBonus(FlushBonusPaytable::try_from(text).map_err(|e| format!("invalid paytable \"{}\": {}", text, e))?)
+    } else if has_flag(args, "--qualify-sensitivity") {
+        Command::QualifySensitivity
+    } else if let Some(text) = flag_str(args, "--conditional")? {
+        Command::Conditional(text.to_string(), parse_cards("--conditional", text)?)
+    } else if let Some(text) = flag_str(args, "--live")? {
+        Command::Live(parse_cards("--live", text)?)
+    } else if let Some(text) = flag_str(args, "--player-hand")? {
+        Command::PlayerHand(PlayerHand::try_from(text).map_err(|e| format!("could not parse --player-hand: {}", e))?)
+    } else if let Some(bankroll) = flag_value(args, "--session")? {
+        let stops = StopRules { stop_win: flag_value(args, "--stop-win")?, stop_loss: flag_value(args, "--stop-loss")? };
+        let bet = flag_value(args, "--bet")?.unwrap_or(1.0);
+        if bet <= 0.0 {
+            return Err(format!("--bet expects a positive amount, got {}", bet));
+        }
+        Command::Session(bankroll, bet, stops)
+    } else if let Some(runs) = flag_value(args, "--repeat")? {
+        Command::Repeat(runs)
+    } else {
+        Command::Baselines
+    };
+    Ok(Invocation { command, seed, verbosity })
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if has_flag(&args, "--help") || has_flag(&args, "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let invocation = match parse_args(&args) {
+        Ok(invocation) => invocation,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&invocation) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(invocation: &Invocation) -> Result<(), Box<dyn Error>> {
+    let base_seed = invocation.base_seed();
+    let verbosity = invocation.verbosity;
+    match &invocation.command {
+        // --bench throughput fails on a slowdown
+        Command::Bench(bench) => match bench.as_str() {
+            "dealing" => {
+                let (by_value, into_buffers) = bench_dealing(10000000);
+                print_summary(
+                    verbosity,
+                    || format!("Dealing 10000000 tables: by value {:?}, into buffers {:?}", by_value, into_buffers),
+                    || format!("bench=dealing tables=10000000 by_value_s={:.6} into_buffers_s={:.6}", by_value.as_secs_f64(), into_buffers.as_secs_f64()),
+                );
+            }
+            "flush" => {
+                let (per_hand, batch) = bench_flush_lengths(1000000)?;
+                print_summary(
+                    verbosity,
+                    || format!("Flush lengths of 1000000 hands: get_best_flush loop {:?}, batch {:?}", per_hand, batch),
+                    || format!("bench=flush hands=1000000 loop_s={:.6} batch_s={:.6}", per_hand.as_secs_f64(), batch.as_secs_f64()),
+                );
+            }
+            _ => {
+                let (elapsed, limit) = (bench_throughput(100000), std::time::Duration::from_secs(10));
+                print_summary(
+                    verbosity,
+                    || format!("100000 seeded deals in {:?} (limit {:?})", elapsed, limit),
+                    || format!("bench=throughput deals=100000 elapsed_s={:.6} limit_s={:.6}", elapsed.as_secs_f64(), limit.as_secs_f64()),
+                );
+                if elapsed > limit {
+                    return Err(format!("throughput regression: 100000 deals took {:?}, over the {:?} limit", elapsed, limit).into());
+                }
+            }
+        },
+        Command::Fixture(path) => {
+            let results = run_fixture(path).map_err(|e| format!("could not run fixture {}: {}", path, e))?;
+            let mismatches: Vec<&FixtureResult> = results.iter().filter(|result| !result.passed()).collect();
+            for mismatch in mismatches.iter() {
+                print_summary(verbosity, || format!("Mismatch at {}", mismatch), || format!("mismatch={:?}", mismatch.to_string()));
+            }
+            print_summary(
+                verbosity,
+                || format!("{} of {} fixture rows passed", results.len() - mismatches.len(), results.len()),
+                || format!("fixture={:?} passed={} rows={}", path, results.len() - mismatches.len(), results.len()),
+            );
+        }
+        // Tables use the columns described at TableStrategy::from_csv
+        Command::Table(path) => {
+            let table = TableStrategy::from_csv(path).map_err(|e| format!("could not load table {}: {}", path, e))?;
+            let loaded = simulate_strategy_seeded(&table, 1000000, base_seed);
+            let builtin = simulate_strategy_seeded(&Jacobson, 1000000, base_seed);
+            print_summary(
+                verbosity,
+                || format!("Table {}: {:.4} per hand, built-in Jacobson {:.4}", path, loaded, builtin),
+                || format!("table={:?} per_hand={:.6} jacobson_per_hand={:.6}", path, loaded, builtin),
+            );
+        }
+        // The format already picks the line layout, so only silent changes anything here
+        Command::OutputFormat(format) => {
+            for (name, strategy) in named_strategies() {
+                let result = simulate_strategy_with(strategy, &GameConfig::STANDARD, 1000000, base_seed);
+                let report = RunReport::new(name, 1000000, base_seed, &result).render(*format);
+                print_summary(verbosity, || report.clone(), || report.clone());
+            }
+        }
+        Command::Until(precision) => {
+            for (name, strategy) in named_strategies() {
+                let (result, deals) = simulate_until(strategy, *precision, 10000000, base_seed);
+                print_summary(
+                    verbosity,
+                    || format!(
+                        "{}: {:.4} ± {:.4} per hand after {} deals, average raise {:.2}x",
+                        name, result.per_hand(), result.confidence_half_width(), deals,
+                        result.avg_raise_multiplier()
+                    ),
+                    || format!(
+                        "strategy={:?} per_hand={:.6} half_width={:.6} deals={} avg_raise={:.6}",
+                        name, result.per_hand(), result.confidence_half_width(), deals,
+                        result.avg_raise_multiplier()
+                    ),
+                );
+            }
+        }
+        Command::Mixed(colluders) => {
+            let colluders = *colluders;
+            let seats: [Box<dyn Strategy>; NUM_PLAYERS] =
+                std::array::from_fn(|seat| if seat < colluders { Box::new(Jacobson) as Box<dyn Strategy> } else { Box::new(Mousseau) });
+            let results = simulate_mixed_table(&seats, &GameConfig::STANDARD, 1000000, base_seed);
+            let (jacobson_seats, mousseau_seats): (Vec<usize>, Vec<usize>) = (0..NUM_PLAYERS).partition(|&seat| seat < colluders);
+            for (name, seats) in [("Eliot Jacobson", jacobson_seats), ("Mousseau", mousseau_seats)] {
+                if !seats.is_empty() {
+                    let per_hand = combine_seats(&results, &seats).per_hand();
+                    print_summary(
+                        verbosity,
+                        || format!("{} on seats {:?}: {:.4} per hand", name, seats, per_hand),
+                        || format!("strategy={:?} seats={:?} per_hand={:.6}", name, seats, per_hand),
+                    );
+                }
+            }
+        }
+        // Seeds found here can be replayed with cargo run --example trace_deal -- SEED
+        Command::FindSeed => {
+            let six_flush = |round: &Round| round.player_hands().iter().any(|hand| get_best_flush(hand).len() >= 6);
+            match find_seed(six_flush, 100000) {
+                Some(seed) => print_summary(
+                    verbosity,
+                    || format!("Seed {} deals a 6+ card flush:\n{}", seed, Round::seeded(seed)),
+                    || format!("seed={}", seed),
+                ),
+                None => print_summary(verbosity, || "No seed below 100000 deals a 6+ card flush".to_string(), || "seed=none".to_string()),
+            }
+        }
+        Command::Significance => {
+            for (name, strategy) in named_strategies() {
+                let (difference, p_value) = paired_significance(strategy, &Mousseau, 100000, base_seed);
+                print_summary(
+                    verbosity,
+                    || format!("{} minus Mousseau: {:+.4} per hand, p = {:.4}", name, difference, p_value),
+                    || format!("strategy={:?} minus_mousseau={:.6} p={:.6}", name, difference, p_value),
+                );
+            }
+        }
+        Command::CollusionSweep => {
+            for (players, edge) in collusion_by_player_count(NUM_PLAYERS, 1000000, base_seed) {
+                print_summary(
+                    verbosity,
+                    || format!("{} colluding seats: {:.4} per hand", players, edge),
+                    || format!("colluders={} per_hand={:.6}", players, edge),
+                );
+            }
+        }
+        Command::Direct => {
+            for (name, strategy) in named_strategies() {
+                let pooled = simulate_strategy_seeded(strategy, 1000000, base_seed);
+                let direct = simulate_direct(strategy, 1000000, base_seed);
+                let results = result_accumulator(strategy, 1000000, base_seed);
+                let percentiles = results.percentiles(&[1.0, 5.0, 50.0, 95.0, 99.0]);
+                let std_dev = results.variance().sqrt();
+                print_summary(
+                    verbosity,
+                    || format!(
+                        "{}: candidate pool {:.4}, direct dealer {:.4}, difference {:.4}\n\
+                        {}: per-hand result percentiles 1/5/50/95/99: {:?}, std dev {:.4}",
+                        name, pooled, direct, pooled - direct, name, percentiles, std_dev
+                    ),
+                    || format!(
+                        "strategy={:?} pool={:.6} direct={:.6} percentiles={:?} std_dev={:.6}",
+                        name, pooled, direct, percentiles, std_dev
+                    ),
+                );
+            }
+            let clairvoyant = clairvoyant_edge(1000000, base_seed);
+            print_summary(
+                verbosity,
+                || format!("Clairvoyant ceiling, direct dealer: {:.4}", clairvoyant),
+                || format!("strategy=\"Clairvoyant\" direct={:.6}", clairvoyant),
+            );
+        }
+        // The exact chance of each best flush category and its play bet, then how often a simulated
+        // dealer qualifies with a longer flush, a 3-card flush or not at all
+        Command::FlushStats => {
+            for (category, probability) in flush_category_probabilities() {
+                let multiplier = category.play_multiplier(&GameConfig::STANDARD);
+                print_summary(
+                    verbosity,
+                    || format!("{}: {:.4}%, plays {}x", category, probability * 100.0, multiplier),
+                    || format!("category={:?} probability={:.6} plays={}", category.to_string(), probability, multiplier),
+                );
+            }
+            let dealer = dealer_qualify_stats(&GameConfig::STANDARD, 1000000, base_seed);
+            let percent = |count: u64| count as f64 * 100.0 / dealer.total() as f64;
+            print_summary(
+                verbosity,
+                || format!(
+                    "Dealer over {} deals: 4+ card flush {:.2}%, 3-card 9-high or better {:.2}%, not qualified {:.2}%",
+                    dealer.total(), percent(dealer.longer), percent(dealer.at_length), percent(dealer.not_qualified)
+                ),
+                || format!(
+                    "dealer_deals={} longer={:.4} at_length={:.4} not_qualified={:.4}",
+                    dealer.total(), percent(dealer.longer), percent(dealer.at_length), percent(dealer.not_qualified)
+                ),
+            );
+            for category in FlushCategory::ALL {
+                let share = percent(dealer.categories.count(category));
+                print_summary(
+                    verbosity,
+                    || format!("Dealer {}: {:.4}%", category, share),
+                    || format!("dealer_category={:?} percent={:.4}", category.to_string(), share),
+                );
+            }
+        }
+        Command::ValidatePool(trials) => {
+            let discrepancy = validate_pool_method(base_seed, *trials);
+            print_summary(
+                verbosity,
+                || format!("Pool average minus direct estimate over {} deals: {:.4} per hand", trials, discrepancy),
+                || format!("deals={} pool_minus_direct={:.6}", trials, discrepancy),
+            );
+        }
+        Command::FlatRaise => {
+            let standard = simulate_strategy_with(&Mousseau, &GameConfig::STANDARD, 1000000, base_seed).per_hand();
+            let flat = simulate_strategy_with(&Mousseau, &GameConfig::STANDARD.flat_raise(), 1000000, base_seed).per_hand();
+            print_summary(
+                verbosity,
+                || format!("Mousseau per hand: standard raise {:.4}, always 1x {:.4}, raise worth {:.4}", standard, flat, standard - flat),
+                || format!("strategy=\"Mousseau\" standard={:.6} flat={:.6} raise_worth={:.6}", standard, flat, standard - flat),
+            );
+        }
+        Command::MaxRaise(cap, capped) => {
+            for (name, strategy) in named_strategies() {
+                let result = simulate_strategy_with(strategy, capped, 1000000, base_seed);
+                print_summary(
+                    verbosity,
+                    || format!("{}: {:.4} per hand with a {}x cap, {} raises capped", name, result.per_hand(), cap, result.capped_raises),
+                    || format!("strategy={:?} per_hand={:.6} cap={} capped_raises={}", name, result.per_hand(), cap, result.capped_raises),
+                );
+            }
+        }
+        Command::FlushBonus(paytable) => {
+            let config = GameConfig::STANDARD.with_flush_bonus(*paytable);
+            for (name, strategy) in named_strategies() {
+                let result = simulate_strategy_with(strategy, &config, 1000000, base_seed);
+                print_summary(
+                    verbosity,
+                    || format!(
+                        "{}: {:.4} per unit on the main game, {:.4} per Flush Bonus bet, {:.4} per unit combined",
+                        name, result.per_unit(), result.flush_bonus_per_bet(), result.combined_per_unit()
+                    ),
+                    || format!(
+                        "strategy={:?} per_unit={:.6} flush_bonus_per_bet={:.6} combined_per_unit={:.6}",
+                        name, result.per_unit(), result.flush_bonus_per_bet(), result.combined_per_unit()
+                    ),
+                );
+            }
+        }
+        Command::QualifySensitivity => {
+            for (qualify_rank, house_edge) in qualify_sensitivity(200000, base_seed) {
+                let cutoff = match qualify_rank {
+                    RANKS => "never".to_string(),
+                    _ => format!("from {}-high", Rank::ALL[qualify_rank]),
+                };
+                print_summary(
+                    verbosity,
+                    || format!("3-card flushes qualify {}: house edge {:.4}", cutoff, house_edge),
+                    || format!("qualify={:?} house_edge={:.6}", cutoff, house_edge),
+                );
+            }
+        }
+        Command::Conditional(text, known) => {
+            let ev = conditional_ev(known, 100000, base_seed)?;
+            print_summary(
+                verbosity,
+                || format!("Average result playing a hand holding {}: {:.4}", text, ev),
+                || format!("holding={:?} ev={:.6}", text, ev),
+            );
+        }
+        Command::Live(cards) => {
+            let mut live = LiveDeck::new();
+            for &card in cards.iter() {
+                live.observe(card).map_err(|e| format!("could not observe {}: {}", card_name(card), e))?;
+            }
+            let qualify = live.qualify_probability(&GameConfig::STANDARD);
+            print_summary(
+                verbosity,
+                || format!(
+                    "{} cards unseen, by suit (d, c, h, s) {:?}\nChance the dealer qualifies: {:.4}",
+                    live.remaining(), live.suit_counts(), qualify
+                ),
+                || format!("unseen={} suits={:?} qualify={:.6}", live.remaining(), live.suit_counts(), qualify),
+            );
+        }
+        Command::PlayerHand(hand) => {
+            let flush = get_best_flush_masked(hand_mask(&hand.0));
+            let multiplier = mousseau_strategy(&hand.0);
+            let ev = direct_ev(&hand.0, 100000, &mut ChaCha8Rng::seed_from_u64(base_seed));
+            print_summary(
+                verbosity,
+                || format!(
+                    "Hand {}\nBest flush {:?}, Mousseau multiplier {}\n\
+                    Average result when played against 100000 random dealer hands: {:.4}",
+                    describe_hand(&hand.0), vec_to_strings(&flush), multiplier, ev
+                ),
+                || format!("best_flush={:?} mousseau={} ev={:.6}", vec_to_strings(&flush).join(" "), multiplier, ev),
+            );
+        }
+        // How often a bankroll busts within 500 hands at the given ante, and how often the stop rules
+        // end the session first
+        Command::Session(bankroll, bet, stops) => {
+            for (name, strategy) in named_strategies() {
+                let exits = session_exits(strategy, &GameConfig::STANDARD, *bankroll, *bet, 500, *stops, 1000, base_seed);
+                let percent = |count: usize| count as f64 / 10.0;
+                let mut verbose = format!(
+                    "{}: {:.1}% of 500-hand sessions bust a {} bankroll betting {} a hand", name, percent(exits.ruined), bankroll, bet
+                );
+                if *stops != StopRules::NONE {
+                    verbose += &format!(
+                        "\n{}: stopped on a win {:.1}%, on a loss {:.1}%, played out {:.1}%",
+                        name, percent(exits.stop_win), percent(exits.stop_loss), percent(exits.completed)
+                    );
+                }
+                print_summary(
+                    verbosity,
+                    || verbose.clone(),
+                    || format!(
+                        "strategy={:?} bankroll={} bet={} ruined={:.1} stop_win={:.1} stop_loss={:.1} completed={:.1}",
+                        name, bankroll, bet, percent(exits.ruined), percent(exits.stop_win), percent(exits.stop_loss), percent(exits.completed)
+                    ),
+                );
+            }
+        }
+        Command::Repeat(runs) => {
+            for (name, strategy) in named_strategies() {
+                let result = repeated_simulation(strategy, 1000000, *runs, base_seed)?;
+                print_summary(
+                    verbosity,
+                    || format!("{} over {} runs: mean {:.4}, run-to-run std dev {:.4}", name, result.runs, result.mean, result.std_dev),
+                    || format!("strategy={:?} runs={} mean={:.6} std_dev={:.6}", name, result.runs, result.mean, result.std_dev),
+                );
+            }
+        }
+        Command::Baselines => run_baselines(invocation.seed, verbosity),
+    }
+    Ok(())
+}
+
+/// The default run: each baseline strategy's summary, with --seed making the perfect collusion run replayable
+fn run_baselines(seed: Option<u64>, verbosity: Verbosity) {
+    let collusion = match seed {
+        Some(seed) => perfect_collusion_sim_seeded(1000000, seed),
+        None => perfect_collusion_sim(1000000),
+    };
+    print_summary(
+        verbosity,
+        || format!(
+            "Perfect Collusion Strategy Results:\n\
+            Total Simulated Hands: {}\n\
+            Total Winnings: {:.2}\n\
+            Average Winnings per Hand: {:.4}",
+            collusion.hands,
+            collusion.net,
+            collusion.per_hand()
+        ),
+        || format!("strategy=perfect_collusion hands={} net={:.2} per_hand={:.6}", collusion.hands, collusion.net, collusion.per_hand()),
+    );
+    let mousseau = simulate_mousseau_strategy(1000000, &GameConfig::STANDARD);
+    print_summary(
+        verbosity,
+        || format!(
+            "Mousseau Strategy Results:\n\
+            Total Simulated Hands: {}\n\
+            Total Winnings: {:.2}\n\
+            Average Winnings per Wager: {:.4}",
+            mousseau.hands,
+            mousseau.net,
+            mousseau.per_hand()
+        ),
+        || format!("strategy=mousseau hands={} net={:.2} per_hand={:.6}", mousseau.hands, mousseau.net, mousseau.per_hand()),
+    );
+    let jacobson = ap_heat(1000000).per_hand();
+    print_summary(
+        verbosity,
+        || format!("Eliot Jacobson average net profit per wager: {}", jacobson),
+        || format!("strategy=jacobson per_hand={:.6}", jacobson),
+    );
+}
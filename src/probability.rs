@@ -0,0 +1,129 @@
+//! Exact flush and combination counts
+
+use crate::card::{DEALER_CARDS, DECK_SIZE, HAND_SIZE, RANKS, SUITS};
+use crate::rules::GameConfig;
+
+/// Number of ways to choose k items from n
+pub const fn n_choose_k(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = if k > n - k { n - k } else { k };
+    let mut result = 1;
+    let mut i = 0;
+    while i < k {
+        // Exact at every step since result is C(n, i) times (n - i), a multiple of i + 1
+        result = result * (n - i) / (i + 1);
+        i += 1;
+    }
+    result
+}
+
+/// Number of dealer hands calculate_average_result() averages over, C(10, 7) = 120
+pub const DEALER_COMBINATIONS: u64 = n_choose_k(DEALER_CARDS as u64, HAND_SIZE as u64);
+
+/// Exact probability that a random 7-card hand's best flush has each length, indexed by length
+/// Enumerates all C(52, 7) hands by suit pattern, so it is the reference for simulated flush frequencies
+pub fn flush_length_probabilities() -> [f64; HAND_SIZE + 1] {
+    let total = n_choose_k(DECK_SIZE as u64, HAND_SIZE as u64) as f64;
+    enumerate_flush_categories(RANKS).map(|count| count as f64 / total)
+}
+
+/// Number of 7-card hands from a deck with `ranks` cards in each suit whose best flush has each
+/// length. The suits are symmetric, so only canonical suit-count patterns (counts high to low) are
+/// visited, each weighted by how many suit assignments share it
+pub fn enumerate_flush_categories(ranks: usize) -> [u64; HAND_SIZE + 1] {
+    let ways = |count: usize| n_choose_k(ranks as u64, count as u64);
+    let mut categories = [0; HAND_SIZE + 1];
+    for (a, category) in categories.iter_mut().enumerate() {
+        for b in 0..=a.min(HAND_SIZE - a) {
+            for c in 0..=b.min(HAND_SIZE - a - b) {
+                let d = HAND_SIZE - a - b - c;
+                if d > c {
+                    continue;
+                }
+                let pattern = [a, b, c, d];
+                *category += suit_orbit_size(&pattern) * pattern.iter().map(|&count| ways(count)).product::<u64>();
+            }
+        }
+    }
+    categories
+}
+
+/// Length of a 7-card hand's best flush as a named bucket. Every 7-card hand holds at least a
+/// 2-card flush, and a 2-2-2-1 suit split holds nothing longer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FlushCategory {
+    TwoCard,
+    ThreeCard,
+    FourCard,
+    FiveCard,
+    SixCard,
+    SevenCard,
+}
+impl FlushCategory {
+    pub const ALL: [FlushCategory; 6] = [
+        FlushCategory::TwoCard, FlushCategory::ThreeCard, FlushCategory::FourCard,
+        FlushCategory::FiveCard, FlushCategory::SixCard, FlushCategory::SevenCard,
+    ];
+    /// Category of a best flush with `len` cards, None outside 2 to HAND_SIZE (a hand dealt under
+    /// GameConfig::for_hand_size() can hold a longer flush)
+    pub fn from_len(len: usize) -> Option<Self> {
+        len.checked_sub(2).and_then(|index| FlushCategory::ALL.get(index)).copied()
+    }
+    /// Cards in a flush of this category
+    pub fn cards(self) -> usize {
+        self as usize + 2
+    }
+    /// Play bet in antes a hand in this category raises under `config`
+    pub fn play_multiplier(self, config: &GameConfig) -> i32 {
+        config.play_multiplier(self.cards())
+    }
+}
+impl std::fmt::Display for FlushCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}-card flush", self.cards())
+    }
+}
+
+/// How many best flushes fell in each category
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FlushCategoryCounts {
+    counts: [u64; 6],
+}
+impl FlushCategoryCounts {
+    /// Counts a best flush of `len` cards, returning its category, or None (and counting nothing)
+    /// for a length without one
+    pub fn record(&mut self, len: usize) -> Option<FlushCategory> {
+        let category = FlushCategory::from_len(len)?;
+        self.counts[category as usize] += 1;
+        Some(category)
+    }
+    pub fn count(&self, category: FlushCategory) -> u64 {
+        self.counts[category as usize]
+    }
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
+/// flush_length_probabilities() bucketed by category, shortest first
+pub fn flush_category_probabilities() -> [(FlushCategory, f64); 6] {
+    let probabilities = flush_length_probabilities();
+    FlushCategory::ALL.map(|category| (category, probabilities[category.cards()]))
+}
+
+/// Distinct ways to give a sorted suit-count pattern to the four suits, 4! divided by repeated counts
+pub(crate) fn suit_orbit_size(pattern: &[usize; SUITS]) -> u64 {
+    let mut size = 24;
+    let mut run = 1;
+    for i in 1..SUITS {
+        if pattern[i] == pattern[i - 1] {
+            run += 1;
+            size /= run;
+        } else {
+            run = 1;
+        }
+    }
+    size
+}
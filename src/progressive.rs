@@ -0,0 +1,95 @@
+//! Progressive side bet
+
+use rand_chacha::ChaCha8Rng;
+use rand::SeedableRng;
+use crate::card::{DEALER_CARDS, HAND_SIZE, NUM_PLAYERS};
+use crate::deck::Deck;
+use crate::evaluator::{flushes_by_suit, straight_flush_len};
+
+/// A 1-unit progressive side bet paid on straight flushes
+/// contribution is the share of each wager added to the jackpot meter, a full 7-card straight flush wins the
+/// meter (which then restarts at seed_amount), and shorter straight flushes pay the fixed paytable in units
+pub struct ProgressiveBet {
+    pub seed_amount: f64,
+    pub contribution: f64,
+    pub paytable: [f64; HAND_SIZE + 1],
+    pub meter: f64,
+}
+impl ProgressiveBet {
+    pub fn new(seed_amount: f64, contribution: f64, paytable: [f64; HAND_SIZE + 1]) -> Self {
+        ProgressiveBet { seed_amount, contribution, paytable, meter: seed_amount }
+    }
+    /// Settles one hand's wager on its longest straight flush in any suit, see ProgressivePayout
+    pub fn play(&mut self, hand: &[usize; HAND_SIZE]) -> ProgressivePayout {
+        self.meter += self.contribution;
+        let longest = flushes_by_suit(hand).iter().map(|flush| straight_flush_len(flush)).max().unwrap_or(0);
+        match longest {
+            HAND_SIZE => {
+                let paid = self.meter;
+                self.meter = self.seed_amount;
+                ProgressivePayout { paid, jackpot: true }
+            }
+            len => ProgressivePayout { paid: self.paytable[len], jackpot: false },
+        }
+    }
+}
+impl Default for ProgressiveBet {
+    fn default() -> Self {
+        ProgressiveBet::new(10000.0, 0.1, [0.0, 0.0, 0.0, 7.0, 60.0, 400.0, 2000.0, 0.0])
+    }
+}
+
+/// One hand's settlement: the amount paid back (0 on a loss) and whether it took the meter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressivePayout {
+    pub paid: f64,
+    pub jackpot: bool,
+}
+
+/// Totals for the progressive side bet, paid includes jackpot_paid
+pub struct ProgressiveResult {
+    pub hands: usize,
+    pub paid: f64,
+    pub jackpot_paid: f64,
+    pub jackpots: usize,
+    pub contribution: f64,
+    pub final_meter: f64,
+}
+impl ProgressiveResult {
+    /// Return to player actually realized in the simulation: units paid back per unit wagered
+    pub fn rtp(&self) -> f64 {
+        self.paid / self.hands as f64
+    }
+    /// Return to player over an endless session, where every contribution is eventually paid out by a
+    /// jackpot: the fixed paytable's return plus the contribution rate (the seed amount is the house's cost)
+    pub fn long_run_rtp(&self) -> f64 {
+        (self.paid - self.jackpot_paid) / self.hands as f64 + self.contribution
+    }
+}
+
+/// Every seat plays the progressive on each of `iterations` seeded deals, with the meter carried across deals
+pub fn simulate_progressive(bet: &mut ProgressiveBet, iterations: usize, seed: u64) -> ProgressiveResult {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_cards = [0; DEALER_CARDS];
+    let mut result = ProgressiveResult {
+        hands: 0, paid: 0.0, jackpot_paid: 0.0, jackpots: 0, contribution: bet.contribution, final_meter: bet.meter,
+    };
+
+    for _ in 0..iterations {
+        deck.shuffle_with(&mut rng);
+        deck.deal_into(&mut hands, &mut dealer_cards);
+        for hand in hands.iter() {
+            let payout = bet.play(hand);
+            if payout.jackpot {
+                result.jackpots += 1;
+                result.jackpot_paid += payout.paid;
+            }
+            result.hands += 1;
+            result.paid += payout.paid;
+        }
+    }
+    result.final_meter = bet.meter;
+    result
+}
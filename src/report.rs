@@ -0,0 +1,50 @@
+//! Machine readable reports
+
+use serde::Serialize;
+
+use crate::simulation::SimulationResult;
+
+/// How --output-format renders a simulation run
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    // One readable line
+    Human,
+    // A JSON object with the RunReport fields
+    Json,
+    // One CSV row, columns in RunReport::CSV_HEADER order, for appending runs to a results file
+    Csv,
+}
+
+/// One strategy's simulation run, as printed by --output-format
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RunReport {
+    pub strategy: String,
+    pub iterations: usize,
+    pub seed: u64,
+    // Net antes won per hand
+    pub edge: f64,
+    pub std_err: f64,
+}
+impl RunReport {
+    pub const CSV_HEADER: &'static str = "strategy,iterations,seed,edge,std_err";
+
+    pub fn new(strategy: &str, iterations: usize, seed: u64, result: &SimulationResult) -> Self {
+        RunReport { strategy: strategy.to_string(), iterations, seed, edge: result.per_hand(), std_err: result.std_err() }
+    }
+
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Human => format!(
+                "{}: {:.4} ± {:.4} per hand over {} deals (seed {})",
+                self.strategy, self.edge, self.std_err, self.iterations, self.seed
+            ),
+            OutputFormat::Json => serde_json::to_string(self).expect("a run report always serializes"),
+            OutputFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+                writer.serialize(self).expect("a run report always serializes");
+                let row = writer.into_inner().expect("writing to a Vec can't fail");
+                String::from_utf8(row).expect("CSV of UTF-8 fields is UTF-8").trim_end().to_string()
+            }
+        }
+    }
+}
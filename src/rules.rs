@@ -0,0 +1,176 @@
+//! Table rules: paytables, qualifiers and tie breaks
+
+use crate::card::{HAND_SIZE, RANKS};
+use crate::evaluator::Flush;
+
+// Boosted blind paytable in antes, indexed by straight flush length (0-2 never pay)
+pub const STRAIGHT_FLUSH_BLIND: [i32; HAND_SIZE + 1] = [0, 0, 0, 1, 2, 5, 25, 100];
+
+/// Flush Bonus side bet odds, indexed by the player's flush length. None loses the bet and Some(0)
+/// pushes it. The bet is settled on the player's own cards, whatever the dealer holds and whether the
+/// seat plays
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlushBonusPaytable {
+    pub odds: [Option<i32>; HAND_SIZE + 1],
+}
+impl Default for FlushBonusPaytable {
+    // A common schedule: 1 to 1 on a 4-card flush, 10 on 5 cards, 100 on 6, 300 on 7
+    fn default() -> Self {
+        FlushBonusPaytable { odds: [None, None, None, None, Some(1), Some(10), Some(100), Some(300)] }
+    }
+}
+impl TryFrom<&str> for FlushBonusPaytable {
+    type Error = Box<dyn std::error::Error>;
+    /// Reads length:odds pairs such as "3:0 4:1 5:10 6:100 7:300", odds of 0 push and lengths left out lose
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let mut odds = [None; HAND_SIZE + 1];
+        for pair in s.split_whitespace() {
+            let (len, pays) = pair.split_once(':').ok_or_else(|| format!("expected length:odds, got {}", pair))?;
+            let len: usize = len.parse()?;
+            if len == 0 || len > HAND_SIZE {
+                return Err(format!("flush length {} is not 1-{}", len, HAND_SIZE).into());
+            }
+            odds[len] = Some(pays.parse()?);
+        }
+        Ok(FlushBonusPaytable { odds })
+    }
+}
+
+/// Whether the dealer qualification rule applies
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QualifyMode {
+    // Dealer needs a 3-card 9-high flush or better, otherwise the player wins the ante
+    Standard,
+    // Dealer always qualifies, for studying flush strength without the qualification rule
+    AlwaysQualify,
+}
+
+/// What the ante gets when the dealer doesn't qualify
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonQualifyRule {
+    // Standard game, the ante wins even money
+    PayAnte,
+    // The ante is returned
+    Push,
+}
+
+/// How two flushes of equal length and ranks are settled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TieBreak {
+    // Standard game, the hand pushes
+    Push,
+    // Higher suit wins, spades > hearts > clubs > diamonds
+    SuitRank,
+    // Highest non-flush card wins, then the next highest and so on
+    HighestKicker,
+}
+
+/// Table rule options for compare_hands_with()
+#[derive(Clone, Copy, Debug)]
+pub struct GameConfig {
+    // Extra antes paid on a winning hand keyed by the straight flush length inside the best flush, not
+    // paid when the dealer sits out. None is the standard game with no blind bonus
+    pub straight_flush_blind: Option<[i32; HAND_SIZE + 1]>,
+    pub qualify_mode: QualifyMode,
+    // Shortest flush the dealer can qualify with, 3 in the standard game. Longer flushes always qualify
+    pub qualify_len: usize,
+    // Lowest top card rank (0 = 2 up to 12 = Ace) a qualify_len flush needs to qualify, 7 (9-high) in
+    // the standard game. RANKS means flushes of exactly qualify_len cards never qualify
+    pub qualify_rank: usize,
+    pub non_qualify: NonQualifyRule,
+    // Treat WILD_CARD as a joker that joins the best suit (wild card variant)
+    pub wild_cards: bool,
+    // Antes lost when a seat folds, above 1.0 for variants that also forfeit a forced blind
+    pub fold_cost: f64,
+    // Cards dealt to each seat, 7 in the standard game, see for_hand_size()
+    pub hand_size: usize,
+    // Play bet in antes indexed by the player's flush length, see play_multipliers_for()
+    pub play_multipliers: [i32; RANKS + 1],
+    // Largest play bet in antes the table allows, e.g. Some(2) where a 7-card flush still raises 2x
+    // None is the standard game with no cap
+    pub max_raise: Option<i32>,
+    pub tie_break: TieBreak,
+    // Symmetric variant: the dealer folds whenever this returns false for their best flush, conceding
+    // like a dealer that didn't qualify. None is standard HCF, where the dealer never folds
+    pub dealer_plays: Option<fn(&Flush) -> bool>,
+    // Flush Bonus side bet of 1 unit every seat places each hand, None for no side bet
+    pub flush_bonus: Option<FlushBonusPaytable>,
+}
+impl GameConfig {
+    pub const STANDARD: GameConfig = GameConfig::for_hand_size(HAND_SIZE);
+
+    /// Standard rules for a flush game dealing hand_size cards to each hand
+    pub const fn for_hand_size(hand_size: usize) -> GameConfig {
+        GameConfig {
+            straight_flush_blind: None,
+            qualify_mode: QualifyMode::Standard,
+            qualify_len: 3,
+            qualify_rank: 7,
+            non_qualify: NonQualifyRule::PayAnte,
+            wild_cards: false,
+            fold_cost: 1.0,
+            hand_size,
+            play_multipliers: play_multipliers_for(hand_size),
+            max_raise: None,
+            tie_break: TieBreak::Push,
+            dealer_plays: None,
+            flush_bonus: None,
+        }
+    }
+
+    /// These rules for a player who never raises past the minimum, a 1x play bet on every flush
+    pub const fn flat_raise(self) -> GameConfig {
+        GameConfig { play_multipliers: [1; RANKS + 1], ..self }
+    }
+
+    /// These rules with the play bet capped at max_raise antes, an error for a cap below the 1x minimum
+    pub fn capped_raise(self, max_raise: i32) -> Result<GameConfig, String> {
+        if max_raise < 1 {
+            return Err(format!("the play bet cap must be at least 1 ante, got {}", max_raise));
+        }
+        Ok(GameConfig { max_raise: Some(max_raise), ..self })
+    }
+
+    /// These rules with every seat also betting the Flush Bonus at the given odds
+    pub const fn with_flush_bonus(self, paytable: FlushBonusPaytable) -> GameConfig {
+        GameConfig { flush_bonus: Some(paytable), ..self }
+    }
+
+    /// Play bet in antes for a flush of flush_len cards, play_multipliers capped by max_raise
+    pub fn play_multiplier(&self, flush_len: usize) -> i32 {
+        let multiplier = self.play_multipliers[flush_len];
+        self.max_raise.map_or(multiplier, |cap| multiplier.min(cap))
+    }
+
+    /// True when max_raise cuts the play bet a flush of flush_len cards would otherwise make
+    pub fn raise_capped(&self, flush_len: usize) -> bool {
+        self.max_raise.is_some_and(|cap| self.play_multipliers[flush_len] > cap)
+    }
+
+    /// These rules with the dealer qualifying on any 2-card flush topped by min_rank or better. With 5 or
+    /// more cards every hand holds a 2-card flush, so min_rank is what keeps some dealers out
+    pub const fn two_card_qualifier(self, min_rank: usize) -> GameConfig {
+        GameConfig { qualify_len: 2, qualify_rank: min_rank, ..self }
+    }
+}
+
+/// Play bet multipliers for a hand size: 3x on a flush one card short of the hand or better, 2x on one
+/// two short, 1x below that. For 7 cards that is 1x up to a 4-card flush, 2x for 5 cards, 3x for 6 or 7
+const fn play_multipliers_for(hand_size: usize) -> [i32; RANKS + 1] {
+    let mut multipliers = [1; RANKS + 1];
+    let mut flush_len = 0;
+    while flush_len <= RANKS {
+        if flush_len + 1 >= hand_size {
+            multipliers[flush_len] = 3;
+        } else if flush_len + 2 == hand_size {
+            multipliers[flush_len] = 2;
+        }
+        flush_len += 1;
+    }
+    multipliers
+}
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig::STANDARD
+    }
+}
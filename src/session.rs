@@ -0,0 +1,174 @@
+//! Bankroll sessions with stop rules
+
+use rand_chacha::ChaCha8Rng;
+use rand::SeedableRng;
+use crate::card::HAND_SIZE;
+use crate::deck::Deck;
+use crate::rules::GameConfig;
+use crate::evaluator::{compare_hands_with, flush_bonus_payout, flush_bonus_payout_wild};
+use crate::strategy::{RoundContext, Strategy};
+
+/// How one seat's bankroll fared over a session, all amounts in the bankroll's units
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SessionResult {
+    pub hands_played: usize,
+    pub final_bankroll: f64,
+    pub peak_bankroll: f64,
+    // Largest fall from a running peak
+    pub max_drawdown: f64,
+    pub exit: SessionExit,
+}
+impl SessionResult {
+    pub fn ruined(&self) -> bool {
+        self.exit == SessionExit::Ruined
+    }
+}
+
+/// Why a session ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionExit {
+    // Every hand was played
+    Completed,
+    // The bankroll could no longer cover a hand
+    Ruined,
+    StopWin,
+    StopLoss,
+}
+
+/// Amounts won or lost from the starting bankroll that end a session early, None never stops
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StopRules {
+    pub stop_win: Option<f64>,
+    pub stop_loss: Option<f64>,
+}
+impl StopRules {
+    pub const NONE: StopRules = StopRules { stop_win: None, stop_loss: None };
+}
+
+/// How many sessions ended each way
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExitCounts {
+    pub completed: usize,
+    pub ruined: usize,
+    pub stop_win: usize,
+    pub stop_loss: usize,
+}
+
+/// Plays seat 0 for up to `hands` freshly shuffled deals with an ante of `bet`, settling each played
+/// hand against the dealer's real 7 cards and any Flush Bonus bet (staked at `bet` too). The session
+/// ends in ruin as soon as the bankroll can no longer cover the most one hand can lose: the ante,
+/// the largest play bet the table allows and the side bet
+pub fn simulate_session(strategy: &dyn Strategy, config: &GameConfig, bankroll: f64, bet: f64, hands: usize, seed: u64) -> SessionResult {
+    simulate_session_with_stops(strategy, config, bankroll, bet, hands, StopRules::NONE, seed)
+}
+
+/// Most one hand can lose in antes under the config: the ante, the play bet of a full-hand flush
+/// after any max_raise cap, and the Flush Bonus stake when the table has one
+pub fn max_hand_loss(config: &GameConfig) -> f64 {
+    let side_bet = if config.flush_bonus.is_some() { 1.0 } else { 0.0 };
+    (1.0 + config.play_multiplier(HAND_SIZE) as f64).max(config.fold_cost) + side_bet
+}
+
+/// simulate_session() that also quits once the bankroll is up stop_win or down stop_loss
+pub fn simulate_session_with_stops(
+    strategy: &dyn Strategy,
+    config: &GameConfig,
+    bankroll: f64,
+    bet: f64,
+    hands: usize,
+    stops: StopRules,
+    seed: u64
+) -> SessionResult {
+    let max_loss = bet * max_hand_loss(config);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut result = SessionResult {
+        hands_played: 0,
+        final_bankroll: bankroll,
+        peak_bankroll: bankroll,
+        max_drawdown: 0.0,
+        exit: SessionExit::Completed,
+    };
+
+    while result.hands_played < hands {
+        if result.final_bankroll < max_loss {
+            result.exit = SessionExit::Ruined;
+            break;
+        }
+        deck.shuffle_with(&mut rng);
+        let round = deck.deal_round();
+        let player = round.player(0);
+        let context = RoundContext::new(round.player_hands(), round.dealer_cards(), config);
+        let main_game = if strategy.plays(player, &context) {
+            let mut dealer = [0; HAND_SIZE];
+            dealer.copy_from_slice(&round.dealer_cards()[..HAND_SIZE]);
+            compare_hands_with(player, &dealer, config) as f64
+        } else {
+            -config.fold_cost
+        };
+        let side_bet = config.flush_bonus.map_or(0.0, |paytable| {
+            let payout = if config.wild_cards { flush_bonus_payout_wild(player, &paytable) } else { flush_bonus_payout(player, &paytable) };
+            payout as f64
+        });
+        let net = bet * (main_game + side_bet);
+
+        result.hands_played += 1;
+        result.final_bankroll += net;
+        result.peak_bankroll = result.peak_bankroll.max(result.final_bankroll);
+        result.max_drawdown = result.max_drawdown.max(result.peak_bankroll - result.final_bankroll);
+
+        let change = result.final_bankroll - bankroll;
+        if stops.stop_win.is_some_and(|target| change >= target) {
+            result.exit = SessionExit::StopWin;
+            break;
+        }
+        if stops.stop_loss.is_some_and(|limit| change <= -limit) {
+            result.exit = SessionExit::StopLoss;
+            break;
+        }
+    }
+
+    result
+}
+
+/// Share of `sessions` independent sessions that end in ruin, seeding session i with base_seed + i
+/// (wrapping past u64::MAX)
+pub fn bust_probability(
+    strategy: &dyn Strategy,
+    config: &GameConfig,
+    bankroll: f64,
+    bet: f64,
+    hands: usize,
+    sessions: usize,
+    base_seed: u64
+) -> f64 {
+    let busts = (0..sessions)
+        .filter(|&session| simulate_session(strategy, config, bankroll, bet, hands, base_seed.wrapping_add(session as u64)).ruined())
+        .count();
+    busts as f64 / sessions as f64
+}
+
+/// How `sessions` independent sessions under the stop rules ended, seeding session i with base_seed + i
+/// (wrapping past u64::MAX)
+#[allow(clippy::too_many_arguments)]
+pub fn session_exits(
+    strategy: &dyn Strategy,
+    config: &GameConfig,
+    bankroll: f64,
+    bet: f64,
+    hands: usize,
+    stops: StopRules,
+    sessions: usize,
+    base_seed: u64
+) -> ExitCounts {
+    let mut counts = ExitCounts::default();
+    for session in 0..sessions {
+        match simulate_session_with_stops(strategy, config, bankroll, bet, hands, stops, base_seed.wrapping_add(session as u64)).exit {
+            SessionExit::Completed => counts.completed += 1,
+            SessionExit::Ruined => counts.ruined += 1,
+            SessionExit::StopWin => counts.stop_win += 1,
+            SessionExit::StopLoss => counts.stop_loss += 1,
+        }
+    }
+    counts
+}
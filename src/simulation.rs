@@ -0,0 +1,546 @@
+//! Monte Carlo simulation of whole tables
+
+use rand::thread_rng;
+use rand_chacha::ChaCha8Rng;
+use rand::{Rng, SeedableRng};
+use crate::card::{DEALER_CARDS, HAND_SIZE, NUM_PLAYERS, RANKS, SUITS, add_suit_counts};
+use crate::deck::Deck;
+use crate::rules::GameConfig;
+use crate::evaluator::{Decision, OutcomeCounts, compare_hands, compare_hands_detailed, flush_bonus_payout, flush_bonus_payout_wild, get_best_flush, get_best_flush_masked, hand_mask, table_results, tally_average_result, units_wagered};
+use crate::strategy::{GatedMousseau, Mousseau, NEVER_PLAY_GATE, RoundContext, Strategy, get_strategy, partial_jacobson_plays, should_play, table_suit_counts};
+
+/// Running net result, hands dealt, antes wagered and outcome counts of played hands for a simulation
+/// Outcomes count every dealer hand a played hand was averaged over, not whole hands
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationResult {
+    pub hands: usize,
+    pub folds: usize,
+    pub net: f64,
+    // Sum of each hand's squared net result, for std_err()
+    pub net_squares: f64,
+    pub units: f64,
+    // Sum of played hands' play bet multipliers, for avg_raise_multiplier()
+    pub play_multipliers: u64,
+    // Played hands whose play bet GameConfig::max_raise cut
+    pub capped_raises: u64,
+    pub outcomes: OutcomeCounts,
+    // Flush Bonus side bets placed and their net units, when GameConfig::flush_bonus is set
+    pub flush_bonus_bets: u64,
+    pub flush_bonus_net: f64,
+}
+impl SimulationResult {
+    pub(crate) fn new() -> Self {
+        SimulationResult {
+            hands: 0, folds: 0, net: 0.0, net_squares: 0.0, units: 0.0, play_multipliers: 0, capped_raises: 0,
+            outcomes: OutcomeCounts::default(), flush_bonus_bets: 0, flush_bonus_net: 0.0,
+        }
+    }
+    pub(crate) fn record(&mut self, decision: Decision, net: f64) {
+        self.hands += 1;
+        match decision {
+            Decision::Fold => self.folds += 1,
+            Decision::Play(multiplier) => self.play_multipliers += multiplier as u64,
+        }
+        self.net += net;
+        self.net_squares += net * net;
+        self.units += units_wagered(decision);
+    }
+    /// record() for a played hand along with its outcome counts
+    fn record_played(&mut self, decision: Decision, net: f64, outcomes: &OutcomeCounts) {
+        self.record(decision, net);
+        self.outcomes.add(outcomes);
+    }
+    /// record() for a seat that played at the flush's standard multiplier for `result`, or folded
+    fn record_standard(&mut self, hand: &[usize; HAND_SIZE], plays: bool, result: f64) {
+        let config = GameConfig::STANDARD;
+        if plays {
+            let multiplier = config.play_multiplier(get_best_flush_masked(hand_mask(hand)).len());
+            self.record(Decision::Play(multiplier as u8), result);
+        } else {
+            self.record(Decision::Fold, -config.fold_cost);
+        }
+    }
+    /// Records one seat's Flush Bonus bet settling for net units
+    fn record_flush_bonus(&mut self, net: i32) {
+        self.flush_bonus_bets += 1;
+        self.flush_bonus_net += net as f64;
+    }
+    /// Adds another run's totals, e.g. one chunk of a longer simulation
+    pub(crate) fn merge(&mut self, other: &SimulationResult) {
+        self.hands += other.hands;
+        self.folds += other.folds;
+        self.net += other.net;
+        self.net_squares += other.net_squares;
+        self.units += other.units;
+        self.play_multipliers += other.play_multipliers;
+        self.capped_raises += other.capped_raises;
+        self.outcomes.add(&other.outcomes);
+        self.flush_bonus_bets += other.flush_bonus_bets;
+        self.flush_bonus_net += other.flush_bonus_net;
+    }
+    /// Net antes won per hand dealt
+    pub fn per_hand(&self) -> f64 {
+        self.net / self.hands as f64
+    }
+    /// Standard error of per_hand(), treating hands as independent. Seats at one table share a
+    /// dealer so this understates the spread a little, repeated_simulation() measures it directly
+    pub fn std_err(&self) -> f64 {
+        let n = self.hands as f64;
+        let variance = (self.net_squares - self.net * self.net / n) / (n - 1.0);
+        (variance.max(0.0) / n).sqrt()
+    }
+    /// Half-width of the 95% confidence interval around per_hand(), from std_err()
+    pub fn confidence_half_width(&self) -> f64 {
+        1.96 * self.std_err()
+    }
+    /// Net antes won per ante wagered
+    pub fn per_unit(&self) -> f64 {
+        self.net / self.units
+    }
+    /// Net units won per Flush Bonus bet, 0.0 if none were placed
+    pub fn flush_bonus_per_bet(&self) -> f64 {
+        if self.flush_bonus_bets > 0 { self.flush_bonus_net / self.flush_bonus_bets as f64 } else { 0.0 }
+    }
+    /// Net units won per unit wagered on the main game and the Flush Bonus together
+    pub fn combined_per_unit(&self) -> f64 {
+        (self.net + self.flush_bonus_net) / (self.units + self.flush_bonus_bets as f64)
+    }
+    /// Mean play bet multiplier over played hands, 0.0 if none were played. Longer flushes raise
+    /// more, so a strategy that plays more of them swings further per hand
+    pub fn avg_raise_multiplier(&self) -> f64 {
+        let played = self.hands - self.folds;
+        if played > 0 { self.play_multipliers as f64 / played as f64 } else { 0.0 }
+    }
+    /// Expected win (negative for a loss) per hour in money for a seat betting `ante` per hand at
+    /// `hands_per_hour`. Assumes independent hands that each carry the simulated edge per hand
+    pub fn hourly(&self, ante: f64, hands_per_hour: f64) -> f64 {
+        self.per_hand() * ante * hands_per_hour
+    }
+}
+
+// perfect collusion
+
+pub fn perfect_collusion_sim(num_simulations: usize) -> SimulationResult {
+    perfect_collusion_sim_with(num_simulations, &mut thread_rng())
+}
+
+/// perfect_collusion_sim() with reproducible deals from the given seed
+pub fn perfect_collusion_sim_seeded(num_simulations: usize, seed: u64) -> SimulationResult {
+    perfect_collusion_sim_with(num_simulations, &mut ChaCha8Rng::seed_from_u64(seed))
+}
+
+fn perfect_collusion_sim_with<R: Rng + ?Sized>(num_simulations: usize, rng: &mut R) -> SimulationResult {
+    let mut totals = SimulationResult::new();
+    let mut hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_cards = [0; DEALER_CARDS];
+    let mut deck = Deck::ordered();
+
+    for _ in 0..num_simulations {
+        deck.shuffle_with(rng);
+        deck.deal_into(&mut hands, &mut dealer_cards);
+
+        for (hand, avg_result) in hands.iter().zip(table_results(&hands, &dealer_cards)) {
+            totals.record_standard(hand, avg_result > -1.0, avg_result);
+        }
+    }
+
+    totals
+}
+
+/// Edge per hand with perfect hindsight: each seat sees its result against the real dealer hand
+/// (dealt as in simulate_direct()) and plays only when that beats folding. No strategy can do
+/// better on the same deals, so it is a ceiling for simulate_direct() results
+pub fn clairvoyant_edge(iterations: usize, seed: u64) -> f64 {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_cards = [0; DEALER_CARDS];
+    let mut dealer = [0; HAND_SIZE];
+    let mut total_score = 0.0;
+
+    for _ in 0..iterations {
+        deck.shuffle_with(&mut rng);
+        deck.deal_into(&mut hands, &mut dealer_cards);
+        dealer.copy_from_slice(&dealer_cards[..HAND_SIZE]);
+        for hand in hands.iter() {
+            total_score += (compare_hands(hand, &dealer).net_antes() as f64).max(-GameConfig::STANDARD.fold_cost);
+        }
+    }
+
+    total_score / (iterations * NUM_PLAYERS) as f64
+}
+
+// end of perfect collusion
+
+/// Mousseau at every seat under the config's rules, unseeded. Plays through play_round() like the
+/// other strategy drivers, so the config's multipliers, qualifier and raise cap all apply
+pub fn simulate_mousseau_strategy(iterations: usize, config: &GameConfig) -> SimulationResult {
+    run_strategy(&Mousseau, config, iterations, DealerDraw::CandidatePool, &mut thread_rng())
+}
+
+/// How finely optimize_mousseau_gate_with() walks the threshold grid
+pub enum GateSearch {
+    // Every other rank for each threshold
+    Coarse,
+    // Every rank for each threshold
+    Fine,
+}
+
+/// Finds the 3-card flush gate that gives Mousseau the smallest house edge, running every gate on the
+/// same seeded deals spread over all available threads. NEVER_PLAY_GATE is among the candidates, so
+/// folding every 3-card flush wins if no threshold beats it
+pub fn optimize_mousseau_gate(iterations: usize, seed: u64) -> (usize, usize, usize) {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    optimize_mousseau_gate_with(iterations, seed, GateSearch::Fine, threads)
+}
+
+/// optimize_mousseau_gate() with a choice of grid and thread count
+pub fn optimize_mousseau_gate_with(iterations: usize, seed: u64, search: GateSearch, threads: usize) -> (usize, usize, usize) {
+    let step = match search {
+        GateSearch::Coarse => 2,
+        GateSearch::Fine => 1,
+    };
+    // The three flush cards have distinct ranks, so only strictly falling thresholds are worth testing,
+    // along with folding every 3-card flush
+    let mut gates = Vec::new();
+    for high in (2..RANKS).step_by(step) {
+        for second in (1..high).step_by(step) {
+            for third in (0..second).step_by(step) {
+                gates.push((high, second, third));
+            }
+        }
+    }
+    gates.push(NEVER_PLAY_GATE);
+
+    let chunk_size = gates.len().div_ceil(threads.max(1));
+    let edges: Vec<f64> = std::thread::scope(|scope| {
+        let workers: Vec<_> = gates
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                chunk.iter()
+                    .map(|&gate| simulate_strategy_seeded(&GatedMousseau { gate }, iterations, seed))
+                    .collect::<Vec<f64>>()
+            }))
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    });
+
+    let best = (0..gates.len())
+        .max_by(|&a, &b| edges[a].total_cmp(&edges[b]).then(b.cmp(&a)))
+        .unwrap();
+    gates[best]
+}
+
+pub fn ap_heat(iterations : usize) -> SimulationResult
+{
+    let mut deck = Deck::new(None);
+    let mut totals = SimulationResult::new();
+    let mut players_hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_hand = [0; DEALER_CARDS];
+
+    for _ in 0..iterations
+    {   // Reset Hands
+        deck.shuffle();
+        deck.deal_into(&mut players_hands, &mut dealer_hand);
+
+        // Finds the play/fold strategy for the round depending on remaining suits
+        let mut suit_counts = table_suit_counts(&players_hands).map(|x| RANKS - x);
+        suit_counts.sort();
+        let strategy = get_strategy(suit_counts);
+
+        // Each player bets or folds based on their hand and round strategy
+        let seat_results = table_results(&players_hands, &dealer_hand);
+        for (player, result) in players_hands.iter().zip(seat_results)
+        {
+            totals.record_standard(player, should_play(get_best_flush(player), strategy), result);
+        }
+    }
+
+    // The expected average winning per hand for an individual player is totals.per_hand()
+    totals
+}
+
+/// Jacobson edge per hand when only the first n seats collude, for each n in 2..=max_players
+/// The colluders pool their suits and each plays by partial_jacobson_plays() on the cards none of them
+/// hold. The other seats are as unknown to them as the dealer's cards, so this is the edge of an n-seat
+/// table of colluders, and with all six seats it is plain Jacobson. Every table size replays the same
+/// seeded deals
+pub fn collusion_by_player_count(max_players: usize, iterations: usize, seed: u64) -> Vec<(usize, f64)> {
+    assert!((2..=NUM_PLAYERS).contains(&max_players), "max_players must be 2 to {}, got {}", NUM_PLAYERS, max_players);
+    let mut players_hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_hand = [0; DEALER_CARDS];
+    (2..=max_players)
+        .map(|players| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut deck = Deck::ordered();
+            let mut total_winnings = 0.0;
+            for _ in 0..iterations {
+                deck.shuffle_with(&mut rng);
+                deck.deal_into(&mut players_hands, &mut dealer_hand);
+                let mut seen = [0; SUITS];
+                add_suit_counts(&mut seen, players_hands[..players].iter().flatten());
+                let unseen = seen.map(|x| RANKS - x);
+                let seat_results = table_results(&players_hands, &dealer_hand);
+                for (hand, result) in players_hands[..players].iter().zip(seat_results) {
+                    total_winnings += if partial_jacobson_plays(&get_best_flush(hand), unseen) { result } else { -1.0 };
+                }
+            }
+            (players, total_winnings / (iterations * players) as f64)
+        })
+        .collect()
+}
+
+/// Seeded deals at a table where each seat follows its own strategy, e.g. Jacobson on three seats and
+/// Mousseau on the rest, returning each seat's totals. Every seat sees the same RoundContext, so a
+/// Jacobson seat still reads the whole table's suits. combine_seats() pools the seats of one strategy
+pub fn simulate_mixed_table(
+    seats: &[Box<dyn Strategy>; NUM_PLAYERS],
+    config: &GameConfig,
+    iterations: usize,
+    seed: u64
+) -> [SimulationResult; NUM_PLAYERS] {
+    let strategies: [&dyn Strategy; NUM_PLAYERS] = std::array::from_fn(|seat| seats[seat].as_ref());
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut deck = Deck::ordered();
+    let mut totals = std::array::from_fn(|_| SimulationResult::new());
+    let mut players_hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_hand = [0; DEALER_CARDS];
+
+    for _ in 0..iterations {
+        deck.shuffle_with(&mut rng);
+        deck.deal_into(&mut players_hands, &mut dealer_hand);
+        play_round_mixed(&strategies, config, DealerDraw::CandidatePool, &players_hands, &dealer_hand, &mut totals);
+    }
+
+    totals
+}
+
+/// The given seats' totals from simulate_mixed_table() added together
+pub fn combine_seats(results: &[SimulationResult; NUM_PLAYERS], seats: &[usize]) -> SimulationResult {
+    let mut combined = SimulationResult::new();
+    for &seat in seats.iter() {
+        combined.merge(&results[seat]);
+    }
+    combined
+}
+
+// Deals simulated between precision checks in simulate_until()
+pub(crate) const PRECISION_BATCH: usize = 1000;
+
+/// Simulates seeded deals in batches until the 95% confidence half-width of the edge per hand is at
+/// most target_precision antes, or max_iterations deals have been played. Returns the totals along
+/// with the number of deals used
+pub fn simulate_until(strategy: &dyn Strategy, target_precision: f64, max_iterations: usize, seed: u64) -> (SimulationResult, usize) {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut totals = SimulationResult::new();
+    let mut deals = 0;
+    while deals < max_iterations {
+        let batch = PRECISION_BATCH.min(max_iterations - deals);
+        totals.merge(&run_strategy(strategy, &GameConfig::STANDARD, batch, DealerDraw::CandidatePool, &mut rng));
+        deals += batch;
+        if totals.confidence_half_width() <= target_precision {
+            break;
+        }
+    }
+    (totals, deals)
+}
+
+/// Average net result per hand when every seat follows the strategy, acting in seat order
+pub fn simulate_strategy(strategy: &dyn Strategy, iterations: usize) -> f64 {
+    run_strategy(strategy, &GameConfig::STANDARD, iterations, DealerDraw::CandidatePool, &mut thread_rng()).per_hand()
+}
+
+/// simulate_strategy() with reproducible deals from the given seed
+pub fn simulate_strategy_seeded(strategy: &dyn Strategy, iterations: usize, seed: u64) -> f64 {
+    simulate_strategy_with(strategy, &GameConfig::STANDARD, iterations, seed).per_hand()
+}
+
+/// simulate_strategy_seeded() under the given table rules
+pub fn simulate_strategy_with(strategy: &dyn Strategy, config: &GameConfig, iterations: usize, seed: u64) -> SimulationResult {
+    run_strategy(strategy, config, iterations, DealerDraw::CandidatePool, &mut ChaCha8Rng::seed_from_u64(seed))
+}
+
+/// simulate_strategy_seeded() settling each played hand against one real dealer hand
+/// (the first 7 of the remaining cards) instead of the average over all C(10,7) choices
+pub fn simulate_direct(strategy: &dyn Strategy, iterations: usize, seed: u64) -> f64 {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    run_strategy(strategy, &GameConfig::STANDARD, iterations, DealerDraw::Direct, &mut rng).per_hand()
+}
+
+/// House edge per hand (Mousseau strategy) at each 3-card qualification cutoff, from 2 (any
+/// 3-card flush qualifies, since the lowest one is 4-high) up to RANKS (none do)
+pub fn qualify_sensitivity(iterations: usize, seed: u64) -> Vec<(usize, f64)> {
+    (2..=RANKS)
+        .map(|qualify_rank| {
+            let config = GameConfig { qualify_rank, ..GameConfig::STANDARD };
+            (qualify_rank, -simulate_strategy_with(&Mousseau, &config, iterations, seed).per_hand())
+        })
+        .collect()
+}
+
+/// How a played hand is settled against the dealer's 10 remaining cards
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum DealerDraw {
+    CandidatePool,
+    Direct,
+}
+
+/// Helper function for the simulate_strategy() drivers
+pub(crate) fn run_strategy<R: Rng + ?Sized>(
+    strategy: &dyn Strategy,
+    config: &GameConfig,
+    iterations: usize,
+    draw: DealerDraw,
+    rng: &mut R
+) -> SimulationResult {
+    let mut deck = Deck::ordered();
+    let mut totals = SimulationResult::new();
+    let mut players_hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_hand = [0; DEALER_CARDS];
+
+    for _ in 0..iterations {
+        deck.shuffle_with(rng);
+        deck.deal_into(&mut players_hands, &mut dealer_hand);
+        play_round(strategy, config, draw, &players_hands, &dealer_hand, &mut totals);
+    }
+
+    info!("simulated {} hands: net {:.2}, {:.6} per hand", totals.hands, totals.net, totals.per_hand());
+    totals
+}
+
+/// Plays every seat of one dealt round in turn, recording each seat's result
+pub(crate) fn play_round(
+    strategy: &dyn Strategy,
+    config: &GameConfig,
+    draw: DealerDraw,
+    players_hands: &[[usize; HAND_SIZE]; NUM_PLAYERS],
+    dealer_hand: &[usize; DEALER_CARDS],
+    totals: &mut SimulationResult
+) {
+    let mut seat_totals = std::array::from_fn(|_| SimulationResult::new());
+    play_round_mixed(&[strategy; NUM_PLAYERS], config, draw, players_hands, dealer_hand, &mut seat_totals);
+    for seat in seat_totals.iter() {
+        totals.merge(seat);
+    }
+}
+
+/// play_round() with each seat following its own strategy and keeping its own totals
+fn play_round_mixed(
+    strategies: &[&dyn Strategy; NUM_PLAYERS],
+    config: &GameConfig,
+    draw: DealerDraw,
+    players_hands: &[[usize; HAND_SIZE]; NUM_PLAYERS],
+    dealer_hand: &[usize; DEALER_CARDS],
+    totals: &mut [SimulationResult; NUM_PLAYERS]
+) {
+    debug!("dealt seats {:?} with dealer pool {:?}", players_hands, dealer_hand);
+    let mut context = RoundContext::new(players_hands, dealer_hand, config);
+    for (player, (strategy, totals)) in players_hands.iter().zip(strategies.iter().zip(totals.iter_mut())) {
+        if let Some(paytable) = &config.flush_bonus {
+            let payout = if config.wild_cards { flush_bonus_payout_wild(player, paytable) } else { flush_bonus_payout(player, paytable) };
+            totals.record_flush_bonus(payout);
+        }
+        let plays = strategy.plays(player, &context);
+        debug!("seat {} {}", context.seat, if plays { "plays" } else { "folds" });
+        if plays {
+            let flush_len = get_best_flush_masked(hand_mask(player)).len();
+            if config.raise_capped(flush_len) {
+                totals.capped_raises += 1;
+            }
+            let multiplier = config.play_multiplier(flush_len);
+            let (net, outcomes) = match draw {
+                DealerDraw::CandidatePool => tally_average_result(player, dealer_hand, config),
+                DealerDraw::Direct => {
+                    let mut dealer = [0; HAND_SIZE];
+                    dealer.copy_from_slice(&dealer_hand[..HAND_SIZE]);
+                    let payout = compare_hands_detailed(player, &dealer, config);
+                    let mut outcomes = OutcomeCounts::default();
+                    outcomes.record(payout.class());
+                    (payout.net() as f64, outcomes)
+                }
+            };
+            totals.record_played(Decision::Play(multiplier as u8), net, &outcomes);
+        } else {
+            totals.record(Decision::Fold, -config.fold_cost);
+        }
+        context.reveal(player);
+    }
+}
+
+// repeated runs
+
+/// Grand mean and between-run spread of several independent simulations
+pub struct AggregateResult {
+    pub runs: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+/// Runs the strategy simulation `runs` times, seeding run i with base_seed + i (wrapping past u64::MAX).
+/// An error for no runs, which have no mean
+pub fn repeated_simulation(strategy: &dyn Strategy, iterations: usize, runs: usize, base_seed: u64) -> Result<AggregateResult, String> {
+    if runs == 0 {
+        return Err("repeated_simulation needs at least one run".to_string());
+    }
+    let results: Vec<f64> = (0..runs)
+        .map(|run| simulate_strategy_seeded(strategy, iterations, base_seed.wrapping_add(run as u64)))
+        .collect();
+    let mean = results.iter().sum::<f64>() / runs as f64;
+    let std_dev = if runs > 1 {
+        let variance = results.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (runs - 1) as f64;
+        variance.sqrt()
+    } else {
+        0.0
+    };
+    Ok(AggregateResult { runs, mean, std_dev })
+}
+
+// end of repeated runs
+
+// parallel runs
+
+// Iterations per block in simulate_parallel(). Blocks are merged in order, so the floating point
+// totals don't depend on which thread played which block
+const PARALLEL_BLOCK: usize = 1000;
+
+/// simulate_strategy_with() spread over `threads` threads. Iteration i is dealt from
+/// Deck::new_for_iteration(seed, i), so the result is identical for any thread count
+pub fn simulate_parallel(
+    strategy: &(dyn Strategy + Sync),
+    config: &GameConfig,
+    iterations: usize,
+    seed: u64,
+    threads: usize
+) -> SimulationResult {
+    let blocks = iterations.div_ceil(PARALLEL_BLOCK);
+    let threads = threads.clamp(1, blocks.max(1));
+    let mut block_results: Vec<(usize, SimulationResult)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|thread| scope.spawn(move || {
+                let mut players_hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+                let mut dealer_hand = [0; DEALER_CARDS];
+                (thread..blocks)
+                    .step_by(threads)
+                    .map(|block| {
+                        let mut totals = SimulationResult::new();
+                        for iteration in block * PARALLEL_BLOCK..((block + 1) * PARALLEL_BLOCK).min(iterations) {
+                            Deck::new_for_iteration(seed, iteration as u64).deal_into(&mut players_hands, &mut dealer_hand);
+                            play_round(strategy, config, DealerDraw::CandidatePool, &players_hands, &dealer_hand, &mut totals);
+                        }
+                        (block, totals)
+                    })
+                    .collect::<Vec<_>>()
+            }))
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    });
+
+    block_results.sort_by_key(|&(block, _)| block);
+    let mut totals = SimulationResult::new();
+    for (_, block_totals) in block_results.iter() {
+        totals.merge(block_totals);
+    }
+    totals
+}
+
+// end of parallel runs
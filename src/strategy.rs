@@ -0,0 +1,475 @@
+//! Play or fold strategies for the team seats
+
+use std::collections::BTreeMap;
+use crate::card::{DEALER_CARDS, HAND_SIZE, NUM_PLAYERS, RANKS, SUITS, Rank, add_suit_counts};
+use crate::rules::GameConfig;
+use crate::probability::n_choose_k;
+use crate::evaluator::{Flush, calculate_average_result_with, compare_flush, flush_suit, get_best_flush, get_best_flush_masked, hand_mask};
+
+// Minimum ranks of the highest, second and third card for Mousseau to play a 3-card flush (T-8-6)
+pub const MOUSSEAU_GATE: (usize, usize, usize) = (8, 6, 4);
+// A 3-card flush gate no flush passes, so every 3-card flush folds
+pub const NEVER_PLAY_GATE: (usize, usize, usize) = (RANKS, RANKS, RANKS);
+
+// Determines raise multiplier (0 = fold, 1–3 = raise) based on the Mousseau non-collusion strategy
+pub fn mousseau_strategy<const N: usize>(hand: &[usize; N]) -> u8 {
+    mousseau_strategy_with(hand, &GameConfig::STANDARD)
+}
+
+// mousseau_strategy() under other rules, e.g. GameConfig::for_hand_size(5) raising by the 5-card multipliers
+pub fn mousseau_strategy_with<const N: usize>(hand: &[usize; N], config: &GameConfig) -> u8 {
+    mousseau_strategy_gated(hand, MOUSSEAU_GATE, config)
+}
+
+// mousseau_strategy() with a different 3-card flush gate, raising by the config's play multipliers
+// The gate applies to flushes of the dealer's qualifying length (its first two ranks for a 2-card
+// qualifier), longer flushes always play and shorter ones fold
+pub fn mousseau_strategy_gated<const N: usize>(hand: &[usize; N], gate: (usize, usize, usize), config: &GameConfig) -> u8 {
+    let flush = get_best_flush(hand);
+    let flush_len = flush.len();
+
+    if flush_len > config.qualify_len {
+        config.play_multiplier(flush_len) as u8
+    } else if flush_len == config.qualify_len {
+        let mut ranks: Vec<usize> = flush.iter().map(|&card| Rank::of(card).to_index()).collect();
+        ranks.sort_unstable_by(|a, b| b.cmp(a)); // Descending
+
+        if ranks.iter().zip([gate.0, gate.1, gate.2]).all(|(&rank, min_rank)| rank >= min_rank) {
+            config.play_multiplier(flush_len) as u8
+        } else {
+            0
+        }
+    } else {
+        0
+    }
+}
+
+/// How many cards of each suit the seats hold between them, the signal colluders pool
+pub fn table_suit_counts(hands: &[[usize; HAND_SIZE]; NUM_PLAYERS]) -> [usize; SUITS] {
+    let mut suit_counts = [0; SUITS];
+    add_suit_counts(&mut suit_counts, hands.iter().flatten());
+    suit_counts
+}
+
+/// Why dealer suit signals can't be looked up in the Jacobson table
+#[derive(Debug, PartialEq)]
+pub enum StrategyError {
+    // Signals must be sorted low to high
+    UnsortedSignals([usize; SUITS]),
+    // Signals must add up to the dealer's DEALER_CARDS candidates
+    WrongSignalTotal([usize; SUITS]),
+    // No strategy code for these signals, e.g. more cards of one suit than the suit has
+    UnknownSignals([usize; SUITS]),
+}
+impl std::fmt::Display for StrategyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StrategyError::UnsortedSignals(signals) => write!(f, "dealer suit signals {:?} are not sorted", signals),
+            StrategyError::WrongSignalTotal(signals) => {
+                write!(f, "dealer suit signals {:?} should add up to {}", signals, DEALER_CARDS)
+            }
+            StrategyError::UnknownSignals(signals) => write!(f, "no strategy for dealer suit signals {:?}", signals),
+        }
+    }
+}
+impl std::error::Error for StrategyError {}
+
+/// get_strategy() for signals from outside a deal, checked to be a sorted split of the dealer's
+/// candidate cards like the ones dealer_suit_splits() lists
+pub fn try_get_strategy(signals: [usize; SUITS]) -> Result<usize, StrategyError> {
+    if signals.windows(2).any(|pair| pair[0] > pair[1]) {
+        return Err(StrategyError::UnsortedSignals(signals));
+    }
+    if signals.iter().sum::<usize>() != DEALER_CARDS {
+        return Err(StrategyError::WrongSignalTotal(signals));
+    }
+    Ok(get_strategy(signals))
+}
+
+// Returns the strategy, represented by a number based on the number of suits
+// remaining in the dealer's potential hand
+// Total over any input: sorted signals adding up to 10 never have a smallest count above 2, so
+// the last arm only catches what try_get_strategy() rejects
+pub(crate) fn get_strategy(signals : [usize; SUITS]) -> usize
+{   // Derived from table used in https://www.888casino.com/blog/novelty-games/high-card-flush-collusion
+    match signals[0]
+    {
+        0 => match signals[1]
+        {
+            0 => match signals[2]
+            {
+                0|1 => 7,
+                _ => 5
+            },
+            1 => match signals[2]
+            {
+                1 => 6,
+                2 => 5,
+                _ => 4
+            },
+            2 => match signals[2]
+            {
+                2 => 4,
+                _ => 11
+            },
+            _ => 10
+        },
+        1 => match signals[1]
+        {
+            1 => match signals[2]
+            {
+                1 => 5,
+                2 => 4,
+                _ => 11
+            },
+            2 => match signals[2]
+            {
+                2 => 10,
+                _ => 9
+            },
+            _ => 8
+        },
+        _ => 12
+    }
+}
+
+/// Every sorted split of the dealer's candidate suits with the strategy code get_strategy() gives it,
+/// as (s0, s1, s2, s3, code) rows for reviewing the table as data
+pub fn strategy_decision_tree() -> Vec<(usize, usize, usize, usize, usize)> {
+    dealer_suit_splits()
+        .into_iter()
+        .map(|signals| (signals[0], signals[1], signals[2], signals[3], get_strategy(signals)))
+        .collect()
+}
+
+// Compares the flush given to see if the player should play it
+// based on the strategy given
+// get_strategy() only gives 4-12, other codes extend the same rules: up to 7 is a minimum flush
+// length and 12 or more plays everything
+pub(crate) fn should_play(flush : Flush, strategy : usize) -> bool
+{
+    match strategy
+    {
+        0..=7 => flush.len() >= strategy,
+        8..=11 => flush.len() > 3 || (flush.len() == 3 && Rank::of(flush[0]).to_index() >= strategy),
+        _ => true
+    }
+}
+
+/// What a seat knows when it is its turn to act
+/// revealed_suits counts the suits held by every seat that acted before this one,
+/// table_suits counts the suits held by the whole table (what full collusion shares),
+/// dealer_cards is the dealer's candidate pool for strategies allowed to peek at it,
+/// seat_flushes is every seat's best flush for strategies where seats show each other their longest suit,
+/// and config is the table's rules, for strategies whose thresholds depend on them
+pub struct RoundContext {
+    pub seat: usize,
+    pub revealed_suits: [usize; SUITS],
+    pub table_suits: [usize; SUITS],
+    pub dealer_cards: [usize; DEALER_CARDS],
+    pub seat_flushes: [Flush; NUM_PLAYERS],
+    pub config: GameConfig,
+}
+impl RoundContext {
+    /// Context for the first seat to act, before any hand is revealed
+    pub fn new(hands: &[[usize; HAND_SIZE]; NUM_PLAYERS], dealer_cards: &[usize; DEALER_CARDS], config: &GameConfig) -> Self {
+        let table_suits = table_suit_counts(hands);
+        let seat_flushes = hands.map(|hand| get_best_flush_masked(hand_mask(&hand)));
+        RoundContext { seat: 0, revealed_suits: [0; SUITS], table_suits, dealer_cards: *dealer_cards, seat_flushes, config: *config }
+    }
+    /// Shows the acting seat's hand to the seats after it and moves on to the next seat
+    pub(crate) fn reveal(&mut self, hand: &[usize; HAND_SIZE]) {
+        add_suit_counts(&mut self.revealed_suits, hand);
+        self.seat += 1;
+    }
+}
+
+/// A play/fold rule followed by each seat
+pub trait Strategy {
+    /// Returns true to play the hand, false to fold
+    fn plays(&self, hand: &[usize; HAND_SIZE], context: &RoundContext) -> bool;
+}
+
+/// Mousseau non-collusion strategy, plays whenever mousseau_strategy_with() raises under the table's rules
+pub struct Mousseau;
+impl Strategy for Mousseau {
+    fn plays(&self, hand: &[usize; HAND_SIZE], context: &RoundContext) -> bool {
+        mousseau_strategy_with(hand, &context.config) != 0
+    }
+}
+
+/// Baseline that folds every hand, losing fold_cost each time
+pub struct FoldAll;
+impl Strategy for FoldAll {
+    fn plays(&self, _hand: &[usize; HAND_SIZE], _context: &RoundContext) -> bool {
+        false
+    }
+}
+
+/// Baseline that plays every hand at its flush's multiplier, the cost of never folding
+pub struct PlayAll;
+impl Strategy for PlayAll {
+    fn plays(&self, _hand: &[usize; HAND_SIZE], _context: &RoundContext) -> bool {
+        true
+    }
+}
+
+/// Mousseau with a custom 3-card flush gate, see optimize_mousseau_gate()
+pub struct GatedMousseau {
+    pub gate: (usize, usize, usize),
+}
+impl Strategy for GatedMousseau {
+    fn plays(&self, hand: &[usize; HAND_SIZE], context: &RoundContext) -> bool {
+        mousseau_strategy_gated(hand, self.gate, &context.config) != 0
+    }
+}
+
+/// Jacobson collusion as in ap_heat(), every seat knows the whole table's suits
+pub struct Jacobson;
+impl Strategy for Jacobson {
+    fn plays(&self, hand: &[usize; HAND_SIZE], context: &RoundContext) -> bool {
+        let mut signals = context.table_suits.map(|x| RANKS - x);
+        signals.sort();
+        should_play(get_best_flush(hand), get_strategy(signals))
+    }
+}
+
+/// Play a flush longer than min_len, or exactly min_len long with its highest card of min_rank or better
+/// (min_len 0 always plays)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableRule {
+    pub min_len: usize,
+    pub min_rank: Rank,
+}
+impl TableRule {
+    fn plays(&self, flush: &Flush) -> bool {
+        flush.len() > self.min_len || (flush.len() == self.min_len && flush.first().is_none_or(|&card| Rank::of(card) >= self.min_rank))
+    }
+}
+
+/// Jacobson collusion with its decision table read at runtime instead of compiled into get_strategy()
+/// signals maps each sorted count of suits left to the dealer to a strategy code, rules maps each code to a TableRule
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableStrategy {
+    signals: BTreeMap<[usize; SUITS], usize>,
+    rules: BTreeMap<usize, TableRule>,
+}
+impl TableStrategy {
+    /// Loads a table from a CSV with columns kind,key,value, where each row is either
+    /// "signal,0 1 2 7,4" (sorted dealer suit counts to strategy code) or
+    /// "rule,9,3 J" (strategy code to the minimum flush length and highest rank at that length)
+    pub fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_csv_reader(std::fs::File::open(path)?)
+    }
+
+    /// from_csv() on CSV text from any reader
+    pub(crate) fn from_csv_reader<R: std::io::Read>(reader: R) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut reader = csv::Reader::from_reader(reader);
+        let mut table = TableStrategy { signals: BTreeMap::new(), rules: BTreeMap::new() };
+        for (i, record) in reader.records().enumerate() {
+            let record = record?;
+            // Row 1 is the header
+            let row = i + 2;
+            let field = |column: usize| record.get(column).map(str::trim).ok_or(format!("row {}: missing column {}", row, column + 1));
+            let code = |text: &str| text.parse::<usize>().map_err(|e| format!("row {}: {}", row, e));
+            match field(0)? {
+                "signal" => {
+                    let counts = field(1)?.split_whitespace().map(code).collect::<Result<Vec<usize>, _>>()?;
+                    let mut signals: [usize; SUITS] = counts
+                        .try_into()
+                        .map_err(|counts: Vec<usize>| format!("row {}: expected {} suit counts, got {}", row, SUITS, counts.len()))?;
+                    signals.sort();
+                    if signals.iter().sum::<usize>() != DEALER_CARDS || signals[SUITS - 1] > RANKS {
+                        return Err(format!("row {}: {:?} is not a possible split of {} dealer cards", row, signals, DEALER_CARDS).into());
+                    }
+                    if table.signals.insert(signals, code(field(2)?)?).is_some() {
+                        return Err(format!("row {}: signals {:?} appear more than once", row, signals).into());
+                    }
+                }
+                "rule" => {
+                    let strategy = code(field(1)?)?;
+                    let parts: Vec<&str> = field(2)?.split_whitespace().collect();
+                    let [min_len, min_rank] = parts[..] else {
+                        return Err(format!("row {}: a rule is a flush length and a rank, e.g. \"3 J\"", row).into());
+                    };
+                    let min_rank = match min_rank.chars().collect::<Vec<char>>()[..] {
+                        [c] => Rank::from_char(c),
+                        _ => None,
+                    };
+                    let min_rank = min_rank.ok_or(format!("row {}: invalid rank \"{}\"", row, parts[1]))?;
+                    let rule = TableRule { min_len: code(min_len)?, min_rank };
+                    if table.rules.insert(strategy, rule).is_some() {
+                        return Err(format!("row {}: strategy {} has more than one rule", row, strategy).into());
+                    }
+                }
+                other => return Err(format!("row {}: unknown row kind \"{}\", expected signal or rule", row, other).into()),
+            }
+        }
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Checks that every possible split of the dealer's suits has a strategy code and every code has a rule
+    fn validate(&self) -> Result<(), String> {
+        for signals in dealer_suit_splits() {
+            let strategy = self.signals.get(&signals).ok_or(format!("no strategy for dealer suit counts {:?}", signals))?;
+            if !self.rules.contains_key(strategy) {
+                return Err(format!("strategy {} (dealer suit counts {:?}) has no rule", strategy, signals));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the table in the from_csv() format
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("kind,key,value\n");
+        for (signals, strategy) in self.signals.iter() {
+            let counts: Vec<String> = signals.iter().map(|count| count.to_string()).collect();
+            csv += &format!("signal,{},{}\n", counts.join(" "), strategy);
+        }
+        for (strategy, rule) in self.rules.iter() {
+            csv += &format!("rule,{},{} {}\n", strategy, rule.min_len, rule.min_rank);
+        }
+        csv
+    }
+
+    /// The strategy code for the sorted dealer suit counts
+    pub fn strategy(&self, signals: &[usize; SUITS]) -> Result<usize, StrategyError> {
+        self.signals.get(signals).copied().ok_or(StrategyError::UnknownSignals(*signals))
+    }
+}
+impl Default for TableStrategy {
+    /// The built-in table from get_strategy() and should_play()
+    fn default() -> Self {
+        let signals = dealer_suit_splits().into_iter().map(|signals| (signals, get_strategy(signals))).collect();
+        let mut rules = BTreeMap::new();
+        for strategy in 4..=7 {
+            rules.insert(strategy, TableRule { min_len: strategy, min_rank: Rank::Two });
+        }
+        for strategy in 8..=11 {
+            rules.insert(strategy, TableRule { min_len: 3, min_rank: Rank::ALL[strategy] });
+        }
+        rules.insert(12, TableRule { min_len: 0, min_rank: Rank::Two });
+        TableStrategy { signals, rules }
+    }
+}
+impl Strategy for TableStrategy {
+    fn plays(&self, hand: &[usize; HAND_SIZE], context: &RoundContext) -> bool {
+        let mut signals = context.table_suits.map(|x| RANKS - x);
+        signals.sort();
+        let strategy = self.strategy(&signals).expect("validated tables cover every split of the dealer's cards");
+        self.rules[&strategy].plays(&get_best_flush(hand))
+    }
+}
+
+/// Every sorted count of cards per suit the dealer's candidate pool can hold
+pub(crate) fn dealer_suit_splits() -> Vec<[usize; SUITS]> {
+    let mut splits = Vec::new();
+    for a in 0..=DEALER_CARDS {
+        for b in a..=DEALER_CARDS - a {
+            for c in b..=DEALER_CARDS - a - b {
+                let d = DEALER_CARDS - a - b - c;
+                if d >= c && d <= RANKS {
+                    splits.push([a, b, c, d]);
+                }
+            }
+        }
+    }
+    splits
+}
+
+/// Plays only when the hand's average result over the dealer pool, under the context's table rules,
+/// beats the threshold. A threshold of -1.0 (the cost of folding) is the perfect_collusion_sim() rule
+pub struct EvThreshold {
+    pub threshold: f64,
+}
+impl Default for EvThreshold {
+    fn default() -> Self {
+        EvThreshold { threshold: 0.0 }
+    }
+}
+impl Strategy for EvThreshold {
+    fn plays(&self, hand: &[usize; HAND_SIZE], context: &RoundContext) -> bool {
+        calculate_average_result_with(hand, &context.dealer_cards, &context.config) > self.threshold
+    }
+}
+
+/// Two-seat collusion: seats 0-1, 2-3 and 4-5 pair up and tell each other their longest suit. A seat
+/// plays by partial_jacobson_plays() on the suits left after its own hand and its partner's flush, but
+/// when both hold their flush in the same suit only the stronger one plays (the lower seat on a tie),
+/// so the pair doesn't bet twice on a suit they are splitting
+pub struct SuitSharingPairs;
+impl Strategy for SuitSharingPairs {
+    fn plays(&self, hand: &[usize; HAND_SIZE], context: &RoundContext) -> bool {
+        let own = &context.seat_flushes[context.seat];
+        let partner = &context.seat_flushes[context.seat ^ 1];
+        let mut seen = [0; SUITS];
+        add_suit_counts(&mut seen, hand.iter().chain(partner.iter()));
+        if !partial_jacobson_plays(own, seen.map(|x| RANKS - x)) {
+            return false;
+        }
+        if flush_suit(own) != flush_suit(partner) {
+            return true;
+        }
+        match compare_flush(own, partner) {
+            std::cmp::Ordering::Equal => context.seat < context.seat ^ 1,
+            ordering => ordering == std::cmp::Ordering::Greater,
+        }
+    }
+}
+
+/// Jacobson collusion where a seat only sees its own hand and the hands of earlier seats, playing by
+/// partial_jacobson_plays() on the suits it hasn't seen
+pub struct SequentialJacobson;
+impl Strategy for SequentialJacobson {
+    fn plays(&self, hand: &[usize; HAND_SIZE], context: &RoundContext) -> bool {
+        let mut seen = context.revealed_suits;
+        add_suit_counts(&mut seen, hand);
+        partial_jacobson_plays(&get_best_flush(hand), seen.map(|x| RANKS - x))
+    }
+}
+
+/// Jacobson collusion for a seat that has only seen some of the table. Every card it hasn't seen is
+/// equally likely to be one of the dealer's, so each split of the dealer's cards over the suits has
+/// its hypergeometric chance, and the seat plays when the splits on which the Jacobson table plays the
+/// flush carry at least half of it. With only the dealer's cards unseen this is exactly Jacobson
+pub(crate) fn partial_jacobson_plays(flush: &Flush, unseen: [usize; SUITS]) -> bool {
+    let total = n_choose_k(unseen.iter().sum::<usize>() as u64, DEALER_CARDS as u64) as f64;
+    let mut playing = 0;
+    for_each_dealer_split(unseen, |mut split, ways| {
+        split.sort();
+        if should_play(*flush, get_strategy(split)) {
+            playing += ways;
+        }
+    });
+    playing as f64 / total >= 0.5
+}
+
+/// Calls visit with every count of the dealer's cards per suit (d, c, h, s) that fits in the unseen
+/// cards, and the number of ways the unseen cards can give the dealer that split
+pub(crate) fn for_each_dealer_split<F: FnMut([usize; SUITS], u64)>(unseen: [usize; SUITS], mut visit: F) {
+    let ways = |suit: usize, count: usize| n_choose_k(unseen[suit] as u64, count as u64);
+    for a in 0..=unseen[0].min(DEALER_CARDS) {
+        for b in 0..=unseen[1].min(DEALER_CARDS - a) {
+            for c in 0..=unseen[2].min(DEALER_CARDS - a - b) {
+                let d = DEALER_CARDS - a - b - c;
+                if d <= unseen[3] {
+                    visit([a, b, c, d], ways(0, a) * ways(1, b) * ways(2, c) * ways(3, d));
+                }
+            }
+        }
+    }
+}
+
+/// Every Strategy implementation with its display name
+pub fn named_strategies() -> [(&'static str, &'static dyn Strategy); 7] {
+    [
+        ("Always fold", &FoldAll),
+        ("Always play", &PlayAll),
+        ("Mousseau", &Mousseau),
+        ("Eliot Jacobson", &Jacobson),
+        ("Sequential Jacobson", &SequentialJacobson),
+        ("Suit-sharing pairs", &SuitSharingPairs),
+        ("EV threshold", &EvThreshold { threshold: 0.0 }),
+    ]
+}
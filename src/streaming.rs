@@ -0,0 +1,46 @@
+//! Simulation progress as an async stream
+
+use rand_chacha::ChaCha8Rng;
+use rand::SeedableRng;
+use crate::rules::GameConfig;
+use crate::strategy::Strategy;
+use crate::simulation::{DealerDraw, SimulationResult, run_strategy};
+
+/// Running totals simulate_stream() sends after each chunk of deals
+#[derive(Clone, Copy, Debug)]
+pub struct ProgressUpdate {
+    pub deals: usize,
+    pub total_deals: usize,
+    pub per_hand: f64,
+    pub per_unit: f64,
+}
+
+/// simulate_strategy_with() as a stream of progress updates, one per chunk of deals, for serving
+/// long runs to a UI. The deals run on tokio's blocking pool so the async runtime is never blocked,
+/// and dropping the stream stops the simulation after the current chunk. Must be called from within
+/// a tokio runtime
+pub fn simulate_stream<S: Strategy + Send + 'static>(
+    strategy: S,
+    config: GameConfig,
+    iterations: usize,
+    seed: u64,
+    chunk: usize
+) -> tokio_stream::wrappers::ReceiverStream<ProgressUpdate> {
+    assert!(chunk > 0, "chunk must be at least one deal");
+    let (sender, receiver) = tokio::sync::mpsc::channel(16);
+    tokio::task::spawn_blocking(move || {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut totals = SimulationResult::new();
+        let mut deals = 0;
+        while deals < iterations {
+            let chunk_deals = chunk.min(iterations - deals);
+            totals.merge(&run_strategy(&strategy, &config, chunk_deals, DealerDraw::CandidatePool, &mut rng));
+            deals += chunk_deals;
+            let update = ProgressUpdate { deals, total_deals: iterations, per_hand: totals.per_hand(), per_unit: totals.per_unit() };
+            if sender.blocking_send(update).is_err() {
+                break;
+            }
+        }
+    });
+    tokio_stream::wrappers::ReceiverStream::new(receiver)
+}
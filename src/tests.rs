@@ -0,0 +1,1643 @@
+use std::collections::BTreeMap;
+use rand_chacha::ChaCha8Rng;
+use rand::{RngCore, SeedableRng};
+
+use crate::card::*;
+use crate::deck::*;
+use crate::rules::*;
+use crate::evaluator::*;
+use crate::probability::*;
+use crate::strategy::*;
+use crate::simulation::*;
+use crate::analysis::*;
+use crate::fixture::*;
+use crate::report::*;
+use crate::session::*;
+use crate::progressive::*;
+#[cfg(feature = "async")]
+use crate::streaming::*;
+use crate::live::*;
+use crate::bench::*;
+
+/// Unbiased sample variance of at least two values
+fn sample_variance(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+/// Fake random source that cycles through fixed values, for exact-deal tests
+struct ScriptedRng {
+    values: Vec<u64>,
+    next: usize,
+}
+impl ScriptedRng {
+    fn new(values: Vec<u64>) -> Self {
+        ScriptedRng { values, next: 0 }
+    }
+}
+impl RngCore for ScriptedRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+    fn next_u64(&mut self) -> u64 {
+        let value = self.values[self.next % self.values.len()];
+        self.next += 1;
+        value
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest.iter_mut() {
+            *byte = self.next_u64() as u8;
+        }
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[test]
+fn card_to_string() {
+    // Card to string
+    assert_eq!(tostr(Card::new(0).unwrap()), "2d");
+    assert_eq!(tostr(Card::new(12).unwrap()), "Ad");
+    assert_eq!(tostr(Card::new(24).unwrap()), "Kc");  // King of Clubs
+    assert_eq!(Card::new(51).unwrap().to_string(), "As");
+    let cards = [0, 13, 26, 39];
+    assert_eq!(arr_to_strings(&cards), ["2d", "2c", "2h", "2s"]);
+    let king = Card::new(24).unwrap();
+    assert_eq!((king.rank(), king.suit(), usize::from(king)), (11, 1, 24));
+    assert_eq!(Card::new(DECK_SIZE), None);
+    assert_eq!(Card::new(WILD_CARD), None);
+    assert_eq!((Rank::of(24), Suit::of(24)), (Rank::King, Some(Suit::Clubs)));
+    assert_eq!((Suit::of(WILD_CARD), Suit::of(99)), (None, None));
+    assert_eq!(Rank::ALL.map(|rank| rank.to_string()).concat(), "23456789TJQKA");
+    assert_eq!(Suit::ALL.map(|suit| suit.to_string()).concat(), "dchs");
+    assert!(Rank::ALL.iter().all(|&rank| Rank::from_index(rank.to_index()) == Some(rank)));
+    assert!(Suit::ALL.iter().all(|&suit| Suit::from_index(suit.to_index()) == Some(suit)));
+    assert_eq!((Rank::from_index(RANKS), Suit::from_index(SUITS)), (None, None));
+}
+
+#[test]
+fn string_to_card() {
+    // String to card
+    for card in 0..DECK_SIZE {
+        assert_eq!(parse_card(&card_name(card)), Ok(card));
+    }
+    assert_eq!(parse_card("A"), Err(ParseCardError::WrongLength("A".to_string())));
+    assert_eq!(parse_card("1s"), Err(ParseCardError::BadRank('1')));
+    assert_eq!(parse_card("Ax"), Err(ParseCardError::BadSuit('x')));
+    assert_eq!("Td".parse::<Card>().map(usize::from), Ok(8));
+    assert_eq!("Jo".parse::<Card>(), Err(ParseCardError::BadSuit('o')));
+    assert_eq!("Asx".parse::<Card>(), Err(ParseCardError::WrongLength("Asx".to_string())));
+    assert_eq!(hand_from_strs(&["As", "Ks", "Qs", "Js", "Ts", "2d", "3c"]), Ok([51, 50, 49, 48, 47, 0, 14]));
+    assert_eq!(hand_from_strs(&["As", "Ks"]), Err(ParseHandError::WrongHandSize(2)));
+    assert_eq!(hand_from_strs(&["As", "Ks", "Qs", "Js", "Ts", "2d", "Jo"]), Ok([51, 50, 49, 48, 47, 0, WILD_CARD]));
+    assert_eq!(hand_from_strs(&["As", "1s"]), Err(ParseHandError::Card(ParseCardError::BadRank('1'))));
+    assert_eq!(Rank::from_char('T'), Some(Rank::Ten));
+    assert_eq!(Rank::from_char('1'), None);
+    assert_eq!(Suit::from_char('h'), Some(Suit::Hearts));
+    assert_eq!(Suit::ALL.map(|suit| Suit::from_char(suit.to_string().chars().next().unwrap())), Suit::ALL.map(Some));
+    assert_eq!(Suit::from_char('o'), None);
+    assert_eq!(parse_card_or_joker("Jo"), Ok(WILD_CARD));
+    assert_eq!(parse_card_or_joker("Js"), Ok(48));
+    assert_eq!(parse_card_or_joker("Qo"), Err(ParseCardError::BadSuit('o')));
+}
+
+#[test]
+fn flush_identification() {
+    // flush identification
+    let hand = [12, 11, 9, 25, 24, 23, 40]; // 3-card diamond flush and 3-card clubs flush, clubs higher
+    let flush = get_best_flush(&hand);
+    assert_eq!(flush.len(), 3);
+    assert_eq!(flush[0] / RANKS, 1); // Clubs
+    assert_eq!(flush.top_n(2), &flush[..2]);
+    assert_eq!(flush.top_n(5), &flush[..]); // Only 3 cards to give
+    assert_eq!(hand_mask(&[0, 13, 51]), 1 | 1 << 13 | 1 << 51);
+    assert_eq!(get_best_flush_masked(hand_mask(&hand)), flush);
+    let by_suit = flushes_by_suit(&hand);
+    assert_eq!(by_suit, [vec![12, 11, 9], vec![25, 24, 23], vec![], vec![40]]);
+    assert_eq!(by_suit.iter().map(Vec::len).sum::<usize>(), hand.len());
+    assert_eq!(&by_suit[1][..], &flush[..]);
+    assert_eq!(flushes_by_suit(&[WILD_CARD, 0]).iter().map(Vec::len).sum::<usize>(), 1);
+
+    assert_eq!(best_flush_lengths(&[[39, 40, 41, 42, 51, 5, 18], [0, 1, 2, 15, 16, 30, 40]]), vec![5, 3]);
+}
+
+#[test]
+fn masked_flush_agrees_with_slice_based() {
+    // Masked flush agrees with the slice-based flush on random hands, and no card is dealt twice
+    let mut rng = ChaCha8Rng::seed_from_u64(106);
+    let mut deck = Deck::ordered();
+    for _ in 0..1000 {
+        deck.shuffle_with(&mut rng);
+        assert!(deck.is_permutation());
+        assert!(deal_is_disjoint(&deck.get_player_hands(), &deck.get_dealer_cards()));
+        for hand in deck.get_player_hands().iter() {
+            let ranks = |flush: Flush| flush.iter().map(|card| card % RANKS).collect::<Vec<usize>>();
+            assert_eq!(ranks(get_best_flush(hand)), ranks(get_best_flush_masked(hand_mask(hand))));
+            assert_eq!(best_flush_lengths(&[*hand]), vec![get_best_flush(hand).len()]);
+            assert_eq!(flushes_by_suit(hand).iter().map(Vec::len).sum::<usize>(), HAND_SIZE);
+        }
+    }
+}
+
+#[test]
+fn payout_sign_agrees_with_flush_comparison() {
+    // Once the dealer qualifies, the payout sign agrees with the raw flush comparison
+    let mut rng = ChaCha8Rng::seed_from_u64(106);
+    let mut deck = Deck::ordered();
+    for _ in 0..1000 {
+        deck.shuffle_with(&mut rng);
+        let hands = deck.get_player_hands();
+        let dealer_flush = get_best_flush(&hands[5]);
+        if dealer_flush.len() >= 4 || (dealer_flush.len() == 3 && dealer_flush[0] % RANKS >= 7) {
+            assert_eq!(compare_hands(&hands[0], &hands[5]).net_antes().cmp(&0), player_vs_player(&hands[0], &hands[5]));
+        }
+    }
+}
+
+#[test]
+fn hand_comparison() {
+    // Test hand comparison
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let result = compare_hands(&player, &dealer);
+    assert_eq!(result, Outcome::PlayerWins { ante: 1, play: 2 });
+    assert_eq!(result.net_antes(), 3);
+    // A decided hand wins or loses exactly what was wagered
+    assert_eq!(result.net_antes() as f64, units_wagered(Decision::from_multiplier(mousseau_strategy(&player))));
+}
+
+#[test]
+fn hand_comparison_non_qualified_dealer() {
+    // Test hand comparison with non-qualified dealer
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let dealer_low = [0, 1, 2, 15, 16, 30, 40]; // 3-card diamond flush, too low
+    let result2 = compare_hands(&player, &dealer_low);
+    assert_eq!(result2, Outcome::DealerNotQualified { ante: 1 });
+    assert_eq!(result2.net_antes(), 1);
+    assert_eq!(compare_hands_detailed(&player, &dealer_low, &GameConfig::STANDARD), PayoutBreakdown { ante: 1, play: 0, bonus: 0, class: HandClass::DealerNotQualified });
+    let always_qualify = GameConfig { qualify_mode: QualifyMode::AlwaysQualify, ..GameConfig::STANDARD };
+    assert_eq!(compare_hands_with(&player, &dealer_low, &always_qualify), 3);
+    assert_eq!(compare_hands_with(&dealer_low, &player, &always_qualify), -2);
+    assert_eq!(compare_hands_detailed(&dealer, &player, &GameConfig::STANDARD), PayoutBreakdown { ante: -1, play: -2, bonus: 0, class: HandClass::Loss });
+}
+
+#[test]
+fn straight_flush_blind_caps_long_runs() {
+    // A straight flush longer than the paytable, only possible in a bigger hand, pays the top entry
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let eight_card = [39, 40, 41, 42, 43, 44, 45, 46]; // 2-9 spade straight flush
+    let blind_rules = GameConfig { straight_flush_blind: Some(STRAIGHT_FLUSH_BLIND), ..GameConfig::STANDARD };
+    assert_eq!(straight_flush_len(&get_best_flush(&eight_card)), 8);
+    assert_eq!(
+        compare_hands_detailed(&eight_card, &dealer, &blind_rules),
+        PayoutBreakdown { ante: 1, play: 3, bonus: STRAIGHT_FLUSH_BLIND[HAND_SIZE], class: HandClass::Win }
+    );
+}
+
+#[test]
+fn straight_flush_blind_bonus() {
+    // Test straight flush blind bonus
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let straight = [46, 47, 48, 49, 50, 5, 18]; // 9-K spade straight flush
+    let blind_rules = GameConfig { straight_flush_blind: Some(STRAIGHT_FLUSH_BLIND), ..GameConfig::STANDARD };
+    assert_eq!(straight_flush_len(&get_best_flush(&straight)), 5);
+    assert_eq!(compare_hands(&straight, &dealer).net_antes(), 3);
+    assert_eq!(compare_hands_with(&straight, &dealer, &blind_rules), 3 + STRAIGHT_FLUSH_BLIND[5]);
+    assert_eq!(
+        compare_hands_detailed(&straight, &dealer, &blind_rules),
+        PayoutBreakdown { ante: 1, play: 2, bonus: STRAIGHT_FLUSH_BLIND[5], class: HandClass::Win }
+    );
+    // A dealer who doesn't qualify only pays the ante, the straight flush never won a showdown
+    let dealer_low = [0, 1, 2, 15, 16, 30, 40]; // 3-card diamond flush, too low
+    assert_eq!(
+        compare_hands_detailed(&straight, &dealer_low, &blind_rules),
+        PayoutBreakdown { ante: 1, play: 0, bonus: 0, class: HandClass::DealerNotQualified }
+    );
+    assert_eq!(compare_hands_with(&straight, &dealer_low, &blind_rules), 1);
+}
+
+#[test]
+fn rank_counts_count_each_rank() {
+    // Rank counts tally each rank across suits and skip the joker
+    let straight = [46, 47, 48, 49, 50, 5, 18]; // 9-K spade straight flush
+    assert_eq!(rank_counts(&get_best_flush(&straight)), [0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 0]);
+    let trips = hand_from_strs(&["As", "Ad", "Ks", "Kh", "Kc", "2d", "3c"]).unwrap();
+    assert_eq!(rank_counts(&trips), [1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 2]);
+    assert_eq!(rank_counts(&[WILD_CARD, 51]), rank_counts(&[51]));
+}
+
+#[test]
+fn outcome_net_antes_matches_payout() {
+    // An Outcome nets the same antes as the bets it came from, blind bonus and push variant included
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let straight = [46, 47, 48, 49, 50, 5, 18]; // 9-K spade straight flush
+    let blind_rules = GameConfig { straight_flush_blind: Some(STRAIGHT_FLUSH_BLIND), ..GameConfig::STANDARD };
+    let non_qualify_push = GameConfig { non_qualify: NonQualifyRule::Push, ..GameConfig::STANDARD };
+    assert_eq!(
+        compare_hands_detailed(&straight, &dealer, &blind_rules).outcome(),
+        Outcome::PlayerWins { ante: 1 + STRAIGHT_FLUSH_BLIND[5], play: 2 }
+    );
+    for seed in 0..50 {
+        let round = Deck::new_seeded(seed).deal_round();
+        for config in [GameConfig::STANDARD, blind_rules, non_qualify_push] {
+            for hand in round.player_hands().iter() {
+                let payout = compare_hands_detailed(hand, round.dealer_cards(), &config);
+                assert_eq!(payout.outcome().net_antes(), compare_hands_with(hand, round.dealer_cards(), &config));
+            }
+        }
+    }
+}
+
+#[test]
+fn play_bet_share_of_result() {
+    // The play bet's share of a result is whatever the ante alone doesn't account for
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let dealer_low = [0, 1, 2, 15, 16, 30, 40]; // 3-card diamond flush, too low
+    let always_qualify = GameConfig { qualify_mode: QualifyMode::AlwaysQualify, ..GameConfig::STANDARD };
+    let straight = [46, 47, 48, 49, 50, 5, 18]; // 9-K spade straight flush
+    let blind_rules = GameConfig { straight_flush_blind: Some(STRAIGHT_FLUSH_BLIND), ..GameConfig::STANDARD };
+    assert_eq!(play_bet_marginal(&straight, &dealer, &blind_rules), 2);
+    assert_eq!(ante_only_result(&straight, &dealer, &blind_rules), 1 + STRAIGHT_FLUSH_BLIND[5]);
+    assert_eq!(play_bet_marginal(&player, &dealer_low, &GameConfig::STANDARD), 0); // Pushes when the dealer doesn't qualify
+    for (p, d) in [(&player, &dealer), (&dealer, &player), (&player, &dealer_low), (&straight, &dealer)] {
+        for rules in [&GameConfig::STANDARD, &blind_rules, &always_qualify] {
+            assert_eq!(play_bet_marginal(p, d, rules) + ante_only_result(p, d, rules), compare_hands_with(p, d, rules));
+        }
+    }
+}
+
+#[test]
+fn longest_straight_flush_finds_runs() {
+    // The longest run of consecutive ranks in a flush, with the ace playing low in a wheel
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let straight = [46, 47, 48, 49, 50, 5, 18]; // 9-K spade straight flush
+    let gapped = [39, 41, 43, 45, 47, 5, 18]; // 5-card spade flush, no straight
+    assert_eq!(straight_flush_len(&get_best_flush(&gapped)), 0);
+    assert_eq!(longest_straight_flush(&get_best_flush(&gapped)), 1);
+    assert_eq!(longest_straight_flush(&get_best_flush(&straight)), 5);
+    assert_eq!(longest_straight_flush(&get_best_flush(&player)), 5); // A-2-3-4-5 of spades
+    let wheel = hand_from_strs(&["As", "2s", "3s", "5s", "9s", "Kd", "Qc"]).unwrap();
+    assert_eq!(longest_straight_flush(&get_best_flush(&wheel)), 3);
+    assert_eq!(straight_flush_len(&get_best_flush(&wheel)), 3);
+    let broadway = hand_from_strs(&["Ah", "Kh", "Qh", "Jh", "Th", "2h", "3h"]).unwrap();
+    assert_eq!(longest_straight_flush(&get_best_flush(&broadway)), 5);
+    assert_eq!(longest_straight_flush(&[]), 0);
+    // A flush with no straight in it earns no blind bonus
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let blind_rules = GameConfig { straight_flush_blind: Some(STRAIGHT_FLUSH_BLIND), ..GameConfig::STANDARD };
+    assert_eq!(compare_hands_with(&gapped, &dealer, &blind_rules), 3);
+}
+
+#[test]
+fn deck_snapshot_restores_deal() {
+    // Deck snapshot and restore replays the same deal
+    let mut deck = Deck::new_seeded(244);
+    let saved = deck.snapshot();
+    deck.shuffle();
+    deck.restore(&saved);
+    assert_eq!(deck.get_cards(), saved.get_cards());
+    assert_eq!(deck.get_player_hands(), saved.get_player_hands());
+}
+
+#[test]
+fn deal_into_matches_dealt_hands() {
+    // Dealing into reused buffers gives the same hands as dealing by value and seat by seat
+    let deck = Deck::new_seeded(244);
+    let mut hands = [[0; HAND_SIZE]; NUM_PLAYERS];
+    let mut dealer_cards = [0; DEALER_CARDS];
+    deck.deal_into(&mut hands, &mut dealer_cards);
+    assert_eq!(hands, deck.get_player_hands());
+    assert_eq!(dealer_cards, deck.get_dealer_cards());
+    for (seat, hand) in hands.iter().enumerate() {
+        assert_eq!(deck.seat_hand(seat), *hand);
+    }
+}
+
+#[test]
+fn position_of_finds_each_card() {
+    // Every card is found at its own position in the deck
+    let ordered = Deck::ordered();
+    let deck = Deck::new_seeded(244);
+    for card in 0..DECK_SIZE {
+        assert_eq!(ordered.position_of(card), card);
+        assert_eq!(deck.get_cards()[deck.position_of(card)], card);
+    }
+}
+
+#[test]
+fn repeated_cards_fail_deal_checks() {
+    // A card dealt twice is neither a permutation of the deck nor a disjoint deal
+    let mut repeated = Deck::ordered().get_cards();
+    repeated[1] = 0;
+    assert!(!Deck { cards: repeated, cursor: 0, size: DECK_SIZE }.is_permutation());
+    let mut hands = Deck::ordered().get_player_hands();
+    hands[5][6] = 0;
+    assert!(!deal_is_disjoint(&hands, &Deck::ordered().get_dealer_cards()));
+}
+
+#[test]
+fn cards_outside_deck_fail_checks() {
+    // Checked comparisons and deal checks reject cards past the deck instead of overflowing, while a joker still compares
+    let clubs = [13, 14, 15, 16, 17, 0, 39];
+    assert_eq!(compare_hands_checked(&clubs, &[26, 27, 28, 29, 1, 40, 64]), Err(DealError::NotInDeck(64)));
+    assert_eq!(compare_hands_checked(&[99, 14, 15, 16, 17, 0, 39], &clubs), Err(DealError::NotInDeck(99)));
+    assert_eq!(compare_hands_checked(&clubs, &[26, 27, 28, 29, 1, 40, WILD_CARD]), Ok(compare_hands(&clubs, &[26, 27, 28, 29, 1, 40, WILD_CARD])));
+    let mut hands = Deck::ordered().get_player_hands();
+    hands[0][0] = 70;
+    assert!(!deal_is_disjoint(&hands, &Deck::ordered().get_dealer_cards()));
+}
+
+#[test]
+fn scripted_shuffle_rotates_ordered_deck() {
+    // An all-zero source swaps every position with the front, rotating the ordered deck by one
+    let mut scripted = Deck::ordered();
+    scripted.shuffle_with(&mut ScriptedRng::new(vec![0]) as &mut dyn RngCore);
+    let rotated: Vec<usize> = (1..DECK_SIZE).chain(0..1).collect();
+    assert_eq!(scripted.get_cards().to_vec(), rotated);
+    assert_eq!(scripted.get_player_hands()[0], [1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(scripted.get_dealer_cards()[DEALER_CARDS - 1], 0);
+}
+
+#[test]
+fn overlapping_hands_only_rejected_when_checked() {
+    // Overlapping hands are evaluated on flush structure alone unless checked
+    let clubs = [13, 14, 15, 16, 17, 0, 39];
+    assert_eq!(compare_hands(&clubs, &clubs), Outcome::Push);
+    assert_eq!(compare_hands(&[13, 13, 14, 15, 16, 1, 2], &clubs), compare_hands(&[13, 14, 15, 16, 1, 2], &clubs));
+    assert_eq!(compare_hands_checked(&clubs, &clubs), Err(DealError::DuplicateCard(13)));
+    assert_eq!(compare_hands_checked(&[13, 13, 14, 15, 16, 17, 0], &[39, 40, 41, 42, 43, 26, 27]), Err(DealError::DuplicateCard(13)));
+    assert_eq!(compare_hands_checked(&clubs, &[26, 27, 28, 29, 1, 40, 41]), Ok(compare_hands(&clubs, &[26, 27, 28, 29, 1, 40, 41])));
+}
+
+#[test]
+fn undealt_is_what_a_round_leaves() {
+    // The undealt cards are whatever a round under the rules, or dealing further by hand, leaves over
+    let deck = Deck::new_seeded(244);
+    assert!(deck.undealt(&GameConfig::STANDARD).is_empty());
+    // Six 5-card hands and the dealer pool leave 12 cards, none of them a seat's or the dealer's
+    let five_card = GameConfig::for_hand_size(5);
+    let (hands, dealer_cards) = deck.deal_round_with(&five_card).unwrap();
+    assert!(hands.iter().all(|hand| hand.len() == 5));
+    let undealt = deck.undealt(&five_card);
+    assert_eq!(undealt.len(), 12);
+    let dealt: Vec<usize> = hands.iter().flat_map(|hand| hand.iter()).chain(dealer_cards.iter()).copied().collect();
+    assert!(undealt.iter().all(|card| !dealt.contains(card)));
+    assert_eq!(dealt.len() + undealt.len(), DECK_SIZE);
+    // The standard round matches deal_into()
+    let (standard_hands, standard_dealer) = deck.deal_round_with(&GameConfig::STANDARD).unwrap();
+    assert_eq!(standard_hands, deck.get_player_hands().iter().map(|hand| &hand[..]).collect::<Vec<_>>());
+    assert_eq!(standard_dealer, deck.get_dealer_cards());
+    let mut dealing = deck.clone();
+    dealing.deal_hand(45);
+    assert_eq!(dealing.undealt(&five_card), &deck.cards[45..]);
+    dealing.reset();
+    dealing.try_deal_round().unwrap();
+    assert!(dealing.undealt(&five_card).is_empty());
+    let reduced = Deck::from_subset(&(0..40).collect::<Vec<usize>>()).unwrap();
+    assert_eq!(reduced.deal_round_with(&GameConfig::STANDARD).unwrap_err(), DealError::NotEnoughCards { needed: 52, remaining: 40 });
+}
+
+#[test]
+fn deal_hand_walks_the_deck() {
+    // Dealt hands follow each other down the deck until it runs out, and reset starts over
+    let deck = Deck::new_seeded(244);
+    let mut dealing = deck.clone();
+    let first = dealing.deal_hand(HAND_SIZE).unwrap().to_vec();
+    let second = dealing.deal_hand(HAND_SIZE).unwrap().to_vec();
+    assert_eq!(first, deck.get_player_hands()[0]);
+    assert_eq!(second, deck.get_player_hands()[1]);
+    assert!(first.iter().all(|card| !second.contains(card)));
+    assert_eq!(dealing.deal_hand(DECK_SIZE - 2 * HAND_SIZE).unwrap().len(), DECK_SIZE - 2 * HAND_SIZE);
+    assert_eq!(dealing.deal_hand(1), None);
+    dealing.reset();
+    assert_eq!(dealing.deal_hand(HAND_SIZE).unwrap(), &first[..]);
+}
+
+#[test]
+fn describe_hand_groups_by_suit() {
+    // Hands are described longest suit first, with any joker last
+    assert_eq!(describe_hand(&[7, 13, 51, 3, 49, 50]), "♠: A K Q | ♦: 9 5 | ♣: 2");
+    assert_eq!(describe_hand(&[26, WILD_CARD, 39, 0]), "♠: 2 | ♥: 2 | ♦: 2 | Jo");
+}
+
+#[test]
+fn player_hand_parses_seven_distinct_cards() {
+    // A player hand parses from exactly seven distinct cards
+    let royal = PlayerHand::try_from("As Ks Qs Js Ts 2d 3c").unwrap();
+    assert_eq!(royal, PlayerHand([51, 50, 49, 48, 47, 0, 14]));
+    assert_eq!(PlayerHand::try_from("As Ks Qs Js Ts 2d"), Err(ParseHandError::WrongHandSize(6)));
+    assert_eq!(PlayerHand::try_from("As Ks Qs Js Ts 2d As"), Err(ParseHandError::DuplicateCard(51)));
+    assert_eq!(PlayerHand::try_from("As Ks Qs Js Ts 2d 3x"), Err(ParseHandError::Card(ParseCardError::BadSuit('x'))));
+}
+
+#[test]
+fn deck_parses_from_card_names() {
+    // A deck parses from its 52 card names and rejects short, repeated or joker decks
+    let deck = Deck::new_seeded(244);
+    let named: Vec<String> = deck.get_cards().iter().map(|&card| card_name(card)).collect();
+    let named: Vec<&str> = named.iter().map(|s| s.as_str()).collect();
+    assert_eq!(Deck::from_card_strings(&named).unwrap().get_cards(), deck.get_cards());
+    assert!(Deck::from_card_strings(&named[1..]).is_err());
+    assert_eq!(Deck::try_from(named.join(" ").as_str()).unwrap().get_cards(), deck.get_cards());
+    let mut doubled = named.clone();
+    doubled[1] = doubled[0];
+    assert_eq!(Deck::from_card_strings(&doubled).unwrap_err().to_string(), format!("{} was dealt more than once", named[0]));
+    doubled[1] = "Jo";
+    assert!(Deck::from_card_strings(&doubled).is_err());
+    assert_eq!(Deck::try_from_cards(Deck::ordered().get_cards()).map(|deck| deck.get_cards()), Ok(Deck::ordered().get_cards()));
+}
+
+#[test]
+fn optimal_decision_plays_only_above_fold_cost() {
+    // The hindsight decision plays exactly the hands averaging better than folding, at the standard bet
+    let mut oracle_deck = Deck::ordered();
+    let mut oracle_rng = ChaCha8Rng::seed_from_u64(143);
+    for _ in 0..20 {
+        oracle_deck.shuffle_with(&mut oracle_rng);
+        let dealer_cards = oracle_deck.get_dealer_cards();
+        for hand in oracle_deck.get_player_hands().iter() {
+            let average = calculate_average_result(hand, &dealer_cards);
+            match optimal_decision(hand, &dealer_cards) {
+                Decision::Fold => assert!(average <= -1.0),
+                Decision::Play(multiplier) => {
+                    assert!(average > -1.0);
+                    assert_eq!(multiplier as i32, GameConfig::STANDARD.play_multipliers[get_best_flush(hand).len()]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn qualifying_dealer_hands_cover_pool() {
+    // Qualifying and non-qualifying dealer hands together make up the whole pool
+    let dealer_low = [0, 1, 2, 15, 16, 30, 40]; // 3-card diamond flush, too low
+    let dealer_cards = Deck::new_seeded(244).get_dealer_cards();
+    let qualifying = qualifying_dealer_hands(&dealer_cards);
+    let mut not_qualifying = 0;
+    for_each_dealer_hand(&dealer_cards, &mut |dealer_hand| {
+        if !dealer_qualifies(&get_best_flush_masked(hand_mask(dealer_hand)), &GameConfig::STANDARD) {
+            not_qualifying += 1;
+        }
+    });
+    assert_eq!(qualifying.len() as u64 + not_qualifying, DEALER_COMBINATIONS);
+    assert!(qualifying.iter().all(|hand| compare_hands(&dealer_low, hand).net_antes() != 1));
+}
+
+#[test]
+fn dealer_qualify_stats_split_by_length() {
+    // Dealer qualification splits into a longer flush, one at the qualifying length, or none
+    let mut qualify_stats = DealerQualifyStats::default();
+    for cards in [&[10, 5, 3, 1][..], &[7, 5, 3], &[6, 5, 3]] {
+        qualify_stats.record(&Flush::from_cards(cards), &GameConfig::STANDARD);
+    }
+    assert_eq!((qualify_stats.longer, qualify_stats.at_length, qualify_stats.not_qualified), (1, 1, 1));
+    assert_eq!(qualify_stats.categories.count(FlushCategory::ThreeCard), 2);
+    assert_eq!(qualify_stats.categories.count(FlushCategory::FourCard), 1);
+    assert_eq!(dealer_qualify_stats(&GameConfig::STANDARD, 50, 182).total(), 50);
+}
+
+#[test]
+fn qualify_rank_compares_as_rank() {
+    // The qualify rank is compared as a Rank, and RANKS keeps every flush of exactly qualify_len out
+    let ace_high = Flush::from_cards(&[12, 5, 3]);
+    assert!(dealer_qualifies(&ace_high, &GameConfig { qualify_rank: 12, ..GameConfig::STANDARD }));
+    assert!(!dealer_qualifies(&ace_high, &GameConfig { qualify_rank: RANKS, ..GameConfig::STANDARD }));
+}
+
+#[test]
+fn empty_flush_never_meets_a_zero_qualify_length() {
+    // With qualify_len 0 an empty dealer flush has no top card and doesn't qualify, while any card does
+    let zero_length = GameConfig { qualify_len: 0, qualify_rank: 0, ..GameConfig::STANDARD };
+    assert!(!dealer_qualifies(&Flush::from_cards(&[]), &zero_length));
+    assert!(dealer_qualifies(&Flush::from_cards(&[0]), &zero_length));
+    assert!(dealer_qualifies(&Flush::from_cards(&[]), &GameConfig { qualify_mode: QualifyMode::AlwaysQualify, ..zero_length }));
+}
+
+#[test]
+fn live_deck_observes_each_card_once() {
+    // A live deck loses each observed card once and refuses repeats and cards outside the deck
+    let mut live = LiveDeck::new();
+    assert_eq!((live.remaining(), live.suit_counts()), (DECK_SIZE, [RANKS; SUITS]));
+    assert_eq!(live.observe(parse_card("As").unwrap()), Ok(()));
+    assert_eq!((live.remaining(), live.suit_counts()), (51, [13, 13, 13, 12]));
+    assert_eq!(live.observe(51), Err(DealError::DuplicateCard(51)));
+    assert_eq!(live.observe(DECK_SIZE), Err(DealError::NotInDeck(DECK_SIZE)));
+    assert_eq!(live.remaining(), 51);
+}
+
+#[test]
+fn live_deck_qualify_probability_is_exact() {
+    // A live deck's qualifying chance matches counting every dealer hand of the unseen cards
+    let live_deck = Deck::new_seeded(188);
+    let mut live = LiveDeck::new();
+    for &card in &live_deck.get_cards()[..40] {
+        live.observe(card).unwrap();
+    }
+    let unseen: [usize; 12] = live_deck.get_cards()[40..].try_into().unwrap();
+    let (mut qualifying_hands, mut dealer_hands) = (0, 0);
+    visit_combinations(&unseen, 0, &mut [0; HAND_SIZE], 0, &mut |hand| {
+        dealer_hands += 1;
+        if dealer_qualifies(&get_best_flush_masked(hand_mask(hand)), &GameConfig::STANDARD) {
+            qualifying_hands += 1;
+        }
+    });
+    assert!((live.qualify_probability(&GameConfig::STANDARD) - qualifying_hands as f64 / dealer_hands as f64).abs() < 1e-12);
+}
+
+#[test]
+fn dealer_pool_hands_average_to_pool_result() {
+    // A dealer pool's hands are distinct and average to the pool result
+    let pool_round = Deck::new_seeded(244).deal_round();
+    let pool = pool_round.dealer_pool();
+    let pool_hands = pool.combinations();
+    assert_eq!(pool_hands.len() as u64, DEALER_COMBINATIONS);
+    assert!(pool_hands.iter().enumerate().all(|(i, hand)| !pool_hands[..i].contains(hand)));
+    let pool_player = PlayerHand(*pool_round.player(0));
+    let pool_total: i32 = pool_hands.iter().map(|hand| hand.result(&pool_player)).sum();
+    assert_eq!(pool_total as f64 / DEALER_COMBINATIONS as f64, pool.average_result(&pool_player));
+}
+
+#[test]
+fn flush_keys_order_like_compare_flush() {
+    // Flush keys sort dealt flushes the same way compare_flush() does
+    let mut key_rng = ChaCha8Rng::seed_from_u64(153);
+    let mut key_deck = Deck::ordered();
+    for _ in 0..200 {
+        key_deck.shuffle_with(&mut key_rng);
+        let flushes = key_deck.get_player_hands().map(|hand| get_best_flush_masked(hand_mask(&hand)));
+        for pair in flushes.windows(2) {
+            assert_eq!(flush_key(&pair[0]).cmp(&flush_key(&pair[1])), compare_flush(&pair[0], &pair[1]));
+        }
+    }
+    assert_eq!(flush_key(&get_best_flush_masked(hand_mask(&[51, 50, 49, 0, 14, 27, 3]))), [3, 12, 11, 10, 0, 0, 0, 0]);
+}
+
+#[test]
+fn compare_flush_is_strict_total_order() {
+    // Every pair of equal-length flushes (one in spades, one in hearts) compares like its keys, and
+    // the keys are distinct, so compare_flush() is a strict total order on each length's rank sets:
+    // antisymmetric, transitive, and Equal only for the same ranks
+    for len in 1..=HAND_SIZE {
+        let lanes: Vec<u64> = (0..1u64 << RANKS).filter(|lane| lane.count_ones() as usize == len).collect();
+        let spades: Vec<Flush> = lanes.iter().map(|&lane| flush_from_lane(3, lane)).collect();
+        let hearts: Vec<Flush> = lanes.iter().map(|&lane| flush_from_lane(2, lane)).collect();
+        let keys: Vec<[u8; HAND_SIZE + 1]> = spades.iter().map(flush_key).collect();
+        assert_eq!(keys.iter().collect::<std::collections::BTreeSet<_>>().len(), lanes.len());
+        for (a, key_a) in spades.iter().zip(keys.iter()) {
+            for (b, key_b) in hearts.iter().zip(keys.iter()) {
+                assert_eq!(compare_flush(a, b), key_a.cmp(key_b));
+            }
+        }
+    }
+}
+
+#[test]
+fn table_suit_counts_cover_every_seat() {
+    // The table's suit counts add up every seat's cards
+    let suit_counts = table_suit_counts(&Deck::new_seeded(244).get_player_hands());
+    assert_eq!(suit_counts.iter().sum::<usize>(), NUM_PLAYERS * HAND_SIZE);
+    assert_eq!(table_suit_counts(&Deck::ordered().get_player_hands()), [13, 13, 13, 3]);
+}
+
+#[test]
+fn table_results_match_each_seat() {
+    // Settling the table at once gives each seat its own average result
+    let deck = Deck::new_seeded(244);
+    let seat_results = table_results(&deck.get_player_hands(), &deck.get_dealer_cards());
+    for (seat, hand) in deck.get_player_hands().iter().enumerate() {
+        assert_eq!(seat_results[seat], calculate_average_result(hand, &deck.get_dealer_cards()));
+    }
+}
+
+#[test]
+fn compare_hands_fast_path_matches_full_comparison() {
+    // Settling on the best suit lanes gives the same payout as building both flushes, across rules
+    // and random hands, and equal lanes still reach the tie rule
+    let rules = [
+        GameConfig::STANDARD,
+        GameConfig { non_qualify: NonQualifyRule::Push, straight_flush_blind: Some(STRAIGHT_FLUSH_BLIND), ..GameConfig::STANDARD },
+        GameConfig { qualify_mode: QualifyMode::AlwaysQualify, ..GameConfig::STANDARD },
+        GameConfig { qualify_rank: RANKS, tie_break: TieBreak::HighestKicker, ..GameConfig::STANDARD },
+        GameConfig { tie_break: TieBreak::SuitRank, max_raise: Some(2), ..GameConfig::STANDARD },
+    ];
+    let mut deck = Deck::ordered();
+    let mut rng = ChaCha8Rng::seed_from_u64(167);
+    for _ in 0..2000 {
+        deck.shuffle_with(&mut rng);
+        let player: [usize; HAND_SIZE] = deck.cards[..HAND_SIZE].try_into().unwrap();
+        let dealer: [usize; HAND_SIZE] = deck.cards[HAND_SIZE..2 * HAND_SIZE].try_into().unwrap();
+        for config in rules.iter() {
+            assert_eq!(compare_hands_detailed(&player, &dealer, config), compare_hands_full(&player, &dealer, config));
+        }
+    }
+    let (player, dealer) = ([12, 11, 10, 13, 14, 26, 39], [25, 24, 23, 0, 1, 26, 39]);
+    for config in rules.iter() {
+        assert_eq!(compare_hands_detailed(&player, &dealer, config), compare_hands_full(&player, &dealer, config));
+    }
+    let suit_rank = GameConfig { tie_break: TieBreak::SuitRank, ..GameConfig::STANDARD };
+    assert_eq!(compare_hands_detailed(&player, &dealer, &suit_rank).class(), HandClass::Loss);
+}
+
+#[test]
+fn table_results_skip_dealer_hands_that_sit_out() {
+    // Skipping dealer hands that sit out gives the same seat results as settling every dealer hand
+    let fast_path_rules = [
+        GameConfig::STANDARD,
+        GameConfig { non_qualify: NonQualifyRule::Push, straight_flush_blind: Some(STRAIGHT_FLUSH_BLIND), ..GameConfig::STANDARD },
+        GameConfig { dealer_plays: Some(dealer_plays_four_flush), qualify_rank: 10, ..GameConfig::STANDARD },
+        GameConfig { qualify_mode: QualifyMode::AlwaysQualify, ..GameConfig::STANDARD },
+    ];
+    let mut fast_path_deck = Deck::ordered();
+    let mut fast_path_rng = ChaCha8Rng::seed_from_u64(167);
+    for _ in 0..20 {
+        fast_path_deck.shuffle_with(&mut fast_path_rng);
+        let (hands, dealer_cards) = (fast_path_deck.get_player_hands(), fast_path_deck.get_dealer_cards());
+        for rules in fast_path_rules.iter() {
+            let reference = hands.map(|hand| calculate_average_result_with(&hand, &dealer_cards, rules));
+            assert_eq!(table_results_with(&hands, &dealer_cards, rules), reference);
+        }
+    }
+}
+
+#[test]
+fn round_validates_dealt_cards() {
+    // A round holds the deck's deal and refuses repeated cards or cards outside the deck
+    let deck = Deck::new_seeded(244);
+    let round = deck.deal_round();
+    assert_eq!(round.player(2), &deck.get_player_hands()[2]);
+    assert_eq!(round.dealer_cards(), &deck.get_dealer_cards());
+    assert_eq!(round.validate(), Ok(()));
+    let mut hands = Deck::ordered().get_player_hands();
+    hands[5][6] = 0;
+    assert_eq!(Round::new(hands, Deck::ordered().get_dealer_cards()).validate(), Err(DealError::DuplicateCard(0)));
+    assert_eq!(Round::new(hands, [60; DEALER_CARDS]).validate(), Err(DealError::NotInDeck(60)));
+}
+
+#[test]
+fn try_deal_round_reports_short_deck() {
+    // Checked dealing reports a deal the deck can't cover instead of running off the end
+    let deck = Deck::new_seeded(244);
+    let round = deck.deal_round();
+    let mut partial = deck.clone();
+    assert_eq!(partial.try_deal_round(), Ok(round));
+    partial.reset();
+    partial.deal_hand(5);
+    let before = partial.snapshot();
+    let short = partial.try_deal_round().unwrap_err();
+    assert_eq!(short, DealError::NotEnoughCards { needed: 52, remaining: 47 });
+    assert_eq!((partial.cursor, partial.cards), (before.cursor, before.cards));
+    assert_eq!(short.to_string(), "the deal needs 52 cards but only 47 are left in the deck");
+}
+
+#[test]
+fn reduced_deck_deals_own_cards() {
+    // A 36-card deck without 2-5 deals and evaluates only its own cards
+    let spanish: Vec<usize> = (0..DECK_SIZE).filter(|card| card % RANKS >= 4).collect();
+    let mut reduced = Deck::from_subset(&spanish).unwrap();
+    assert_eq!(reduced.size(), 36);
+    assert!(reduced.is_permutation());
+    reduced.shuffle_with(&mut ChaCha8Rng::seed_from_u64(185));
+    let mut reduced_hand = [0; HAND_SIZE];
+    for _ in 0..5 {
+        reduced_hand.copy_from_slice(reduced.deal_hand(HAND_SIZE).unwrap());
+        assert!(reduced_hand.iter().all(|&card| card % RANKS >= 4));
+        assert!(get_best_flush(&reduced_hand).iter().all(|&card| card % RANKS >= 4));
+    }
+    assert_eq!(reduced.deal_hand(HAND_SIZE), None); // Only one card left
+    assert_eq!(reduced.undealt(&GameConfig::for_hand_size(4)).len(), 1);
+    reduced.reset();
+    assert_eq!(reduced.try_deal_round(), Err(DealError::NotEnoughCards { needed: 52, remaining: 36 }));
+    assert_eq!(reduced.seat_hand(4), reduced.cards[28..35]);
+    let reduced_flush = get_best_flush(&hand_from_strs(&["6s", "8s", "As", "7h", "9h", "Td", "Jc"]).unwrap());
+    assert_eq!(vec_to_strings(&reduced_flush), vec!["As", "8s", "6s"]);
+    assert_eq!(Deck::from_subset(&[0, 0]).unwrap_err(), DealError::DuplicateCard(0));
+}
+
+#[test]
+#[should_panic(expected = "a full table needs the whole deck")]
+fn reduced_deck_refuses_full_table() {
+    // A reduced deck can't deal a full table, and says so instead of dealing the cards it left out
+    let spanish: Vec<usize> = (0..DECK_SIZE).filter(|card| card % RANKS >= 4).collect();
+    Deck::from_subset(&spanish).unwrap().deal_round();
+}
+
+#[test]
+fn round_context_reveals_each_seat() {
+    // Each seat acting reveals its suits to the seats after it
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let table = Deck::ordered().get_player_hands();
+    let mut context = RoundContext::new(&table, &Deck::ordered().get_dealer_cards(), &GameConfig::STANDARD);
+    assert_eq!(context.table_suits, [13, 13, 13, 3]);
+    context.reveal(&player);
+    assert_eq!(context.seat, 1);
+    assert_eq!(context.revealed_suits, [1, 1, 0, 5]);
+}
+
+#[test]
+fn partial_jacobson_weighs_dealer_splits() {
+    // Splits are weighted by the ways the unseen cards can deal them, and with only the dealer's cards
+    // unseen the one split left decides exactly as Jacobson does
+    let mut ways = 0;
+    for_each_dealer_split([13, 13, 13, 6], |split, count| {
+        assert_eq!(split.iter().sum::<usize>(), DEALER_CARDS);
+        ways += count;
+    });
+    assert_eq!(ways, n_choose_k(45, DEALER_CARDS as u64));
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let flushes = [get_best_flush(&player), get_best_flush(&[0, 2, 4, 13, 15, 26, 28]), get_best_flush(&[12, 7, 2, 13, 15, 26, 28])];
+    for split in dealer_suit_splits() {
+        for flush in flushes.iter() {
+            assert_eq!(partial_jacobson_plays(flush, split), should_play(*flush, get_strategy(split)));
+        }
+    }
+}
+
+#[test]
+fn sequential_jacobson_between_play_all_and_jacobson() {
+    // Seeing only the earlier seats keeps part of Jacobson's edge over playing every hand, not all of it
+    let (over_play_all, p_play_all) = paired_significance(&SequentialJacobson, &PlayAll, 300, 103);
+    assert!(over_play_all > 0.0 && p_play_all < 0.001, "{} over Always play, p = {}", over_play_all, p_play_all);
+    let (under_jacobson, p_jacobson) = paired_significance(&Jacobson, &SequentialJacobson, 300, 103);
+    assert!(under_jacobson > 0.0 && p_jacobson < 0.001, "{} under Jacobson, p = {}", under_jacobson, p_jacobson);
+}
+
+#[test]
+fn repeated_simulation_is_reproducible() {
+    // Repeated runs from one base seed give the same mean and spread every time
+    let first = repeated_simulation(&Mousseau, 200, 3, 7).unwrap();
+    let second = repeated_simulation(&Mousseau, 200, 3, 7).unwrap();
+    assert_eq!(first.runs, 3);
+    assert_eq!(first.mean, second.mean);
+    assert_eq!(first.std_dev, second.std_dev);
+    assert!(first.std_dev > 0.0);
+}
+
+#[test]
+fn repeated_simulation_rejects_zero_runs_and_wraps_seeds() {
+    // Zero runs is an error rather than a NaN, and seeds wrap past u64::MAX instead of overflowing
+    assert!(repeated_simulation(&Mousseau, 200, 0, 7).is_err());
+    let wrapped = repeated_simulation(&Mousseau, 20, 2, u64::MAX).unwrap();
+    let expected = (simulate_strategy_seeded(&Mousseau, 20, u64::MAX) + simulate_strategy_seeded(&Mousseau, 20, 0)) / 2.0;
+    assert!((wrapped.mean - expected).abs() < 1e-12);
+}
+
+#[test]
+fn seeded_decks_are_reproducible() {
+    // A seed always shuffles the deck the same way, and different seeds shuffle it differently
+    assert_eq!(Deck::new_seeded(252).get_cards(), Deck::new_seeded(252).get_cards());
+    assert_ne!(Deck::new_seeded(252).get_cards(), Deck::new_seeded(253).get_cards());
+    assert!(Deck::new_seeded(252).is_permutation());
+}
+
+#[test]
+fn baseline_simulations_return_results() {
+    // The baseline simulations return their tallies, seeded runs reproducibly
+    let collusion = perfect_collusion_sim_seeded(50, 252);
+    assert_eq!(collusion, perfect_collusion_sim_seeded(50, 252));
+    assert_eq!(collusion.hands, 50 * NUM_PLAYERS);
+    assert_eq!(collusion.per_hand(), collusion.net / collusion.hands as f64);
+    assert_eq!(simulate_mousseau_strategy(10, &GameConfig::STANDARD).hands, 10 * NUM_PLAYERS);
+}
+
+#[test]
+fn gate_search_reproducible() {
+    // Gate search replays across thread counts and, given enough deals to separate the gates, picks
+    // one that beats both folding and playing every 3-card flush
+    let gate = optimize_mousseau_gate_with(400, 118, GateSearch::Coarse, 4);
+    assert_eq!(gate, optimize_mousseau_gate_with(400, 118, GateSearch::Coarse, 1));
+    let edge = |gate| simulate_strategy_seeded(&GatedMousseau { gate }, 400, 118);
+    assert!(edge(gate) > edge(NEVER_PLAY_GATE), "{:?} vs never playing", gate);
+    assert!(edge(gate) > edge((2, 1, 0)), "{:?} vs always playing", gate);
+    assert!(gate.0 > gate.1 && gate.1 > gate.2);
+}
+
+#[test]
+fn default_gate_is_mousseau() {
+    // Gated Mousseau at the standard gate plays exactly as Mousseau
+    assert_eq!(simulate_strategy_seeded(&GatedMousseau { gate: MOUSSEAU_GATE }, 50, 118), simulate_strategy_seeded(&Mousseau, 50, 118));
+}
+
+#[test]
+fn direct_sampling_is_reproducible() {
+    // Direct dealer sampling replays from its seed
+    assert_eq!(simulate_direct(&Mousseau, 50, 128), simulate_direct(&Mousseau, 50, 128));
+}
+
+#[test]
+fn paired_comparisons_report_edge_difference() {
+    // Paired comparisons report the edge difference on shared deals and its significance
+    assert!((two_sided_p_value(1.96) - 0.05).abs() < 1e-3);
+    assert_eq!(paired_significance(&Jacobson, &Jacobson, 20, 189), (0.0, 1.0));
+    let (difference, p_value) = paired_significance(&PlayAll, &FoldAll, 50, 189);
+    assert!((difference - (simulate_strategy_seeded(&PlayAll, 50, 189) - simulate_strategy_seeded(&FoldAll, 50, 189))).abs() < 1e-9);
+    assert!(p_value < 0.001);
+}
+
+#[test]
+fn seed_search_finds_first_deal() {
+    // Seed search finds the first deal with a 6+ card player flush
+    let six_flush = |round: &Round| round.player_hands().iter().any(|hand| get_best_flush(hand).len() >= 6);
+    let found = find_seed(six_flush, 10000).unwrap();
+    assert!(six_flush(&Round::seeded(found)) && (0..found).all(|seed| !six_flush(&Round::seeded(seed))));
+    assert_eq!(find_seed(|_| false, 10), None);
+}
+
+#[test]
+fn baselines_fold_or_play_every_hand() {
+    // Always fold folds every hand for the fold cost, and always play never folds
+    let fold_all = simulate_strategy_with(&FoldAll, &GameConfig::STANDARD, 20, 184);
+    assert_eq!((fold_all.folds, fold_all.per_hand(), fold_all.avg_raise_multiplier()), (fold_all.hands, -1.0, 0.0));
+    assert_eq!(simulate_strategy_with(&PlayAll, &GameConfig::STANDARD, 20, 184).folds, 0);
+}
+
+#[test]
+fn sessions_track_bankroll_and_ruin() {
+    // A session of folds loses an ante a hand until the bankroll runs out, and seeded sessions replay
+    let never = EvThreshold { threshold: f64::INFINITY };
+    let folding_session = simulate_session(&never, &GameConfig::STANDARD, 10.0, 1.0, 20, 154);
+    assert_eq!((folding_session.hands_played, folding_session.final_bankroll, folding_session.ruined()), (7, 3.0, true));
+    assert_eq!((folding_session.peak_bankroll, folding_session.max_drawdown), (10.0, 7.0));
+    let session = simulate_session(&Mousseau, &GameConfig::STANDARD, 100.0, 1.0, 50, 154);
+    assert!(!session.ruined() && session.hands_played == 50);
+    assert_eq!(session.final_bankroll, simulate_session(&Mousseau, &GameConfig::STANDARD, 100.0, 1.0, 50, 154).final_bankroll);
+}
+
+#[test]
+fn session_ruin_follows_bet_and_table_limits() {
+    // The bet scales every result and the ruin threshold, which counts the raise cap and the side bet
+    let never = EvThreshold { threshold: f64::INFINITY };
+    let capped = GameConfig::STANDARD.capped_raise(1).unwrap();
+    let with_bonus = GameConfig::STANDARD.with_flush_bonus(FlushBonusPaytable::default());
+    assert_eq!((max_hand_loss(&GameConfig::STANDARD), max_hand_loss(&capped), max_hand_loss(&with_bonus)), (4.0, 2.0, 5.0));
+    // Folding 2 a hand from 10 stops at 6, short of the 8 a full-hand flush could lose
+    let doubled = simulate_session(&never, &GameConfig::STANDARD, 10.0, 2.0, 20, 154);
+    assert_eq!((doubled.hands_played, doubled.final_bankroll, doubled.ruined()), (2, 6.0, true));
+    // With the play bet capped at 1x, 2 is enough to cover a hand
+    let capped_session = simulate_session(&never, &capped, 10.0, 1.0, 20, 154);
+    assert_eq!((capped_session.hands_played, capped_session.final_bankroll), (9, 1.0));
+    let side_bet_session = simulate_session(&Mousseau, &with_bonus, 100.0, 1.0, 50, 154);
+    let plain_session = simulate_session(&Mousseau, &GameConfig::STANDARD, 100.0, 1.0, 50, 154);
+    assert_ne!(side_bet_session.final_bankroll, plain_session.final_bankroll);
+}
+
+#[test]
+fn bust_probability_counts_ruined_sessions() {
+    // Every session of folds busts a bankroll too small for the hands played
+    let never = EvThreshold { threshold: f64::INFINITY };
+    assert_eq!(bust_probability(&never, &GameConfig::STANDARD, 10.0, 1.0, 20, 3, 154), 1.0);
+    assert_eq!(session_exits(&never, &GameConfig::STANDARD, 10.0, 1.0, 20, StopRules::NONE, 3, 154).ruined, 3);
+}
+
+#[test]
+fn bust_probability_wraps_seeds() {
+    // Session seeds wrap past u64::MAX instead of overflowing
+    let ruined = |seed| simulate_session(&Mousseau, &GameConfig::STANDARD, 5.0, 1.0, 20, seed).ruined() as u8 as f64;
+    let expected = (ruined(u64::MAX) + ruined(0)) / 2.0;
+    assert_eq!(bust_probability(&Mousseau, &GameConfig::STANDARD, 5.0, 1.0, 20, 2, u64::MAX), expected);
+}
+
+#[test]
+fn stop_rules_end_sessions_early() {
+    // Stop rules end sessions as soon as the bankroll moves far enough
+    let never = EvThreshold { threshold: f64::INFINITY };
+    let stop_loss = StopRules { stop_win: None, stop_loss: Some(3.0) };
+    let stopped = simulate_session_with_stops(&never, &GameConfig::STANDARD, 10.0, 1.0, 20, stop_loss, 154);
+    assert_eq!((stopped.hands_played, stopped.final_bankroll, stopped.exit), (3, 7.0, SessionExit::StopLoss));
+    let tight = StopRules { stop_win: Some(1.0), stop_loss: Some(1.0) };
+    let exits = session_exits(&Mousseau, &GameConfig::STANDARD, 100.0, 1.0, 50, tight, 20, 166);
+    assert_eq!(exits.stop_win + exits.stop_loss, 20); // 1-ante targets are hit long before 50 hands
+}
+
+#[test]
+fn session_exits_wrap_seeds() {
+    // Session seeds wrap past u64::MAX instead of overflowing
+    let stop_loss = StopRules { stop_win: Some(5.0), stop_loss: Some(5.0) };
+    let mut expected = ExitCounts::default();
+    for seed in [u64::MAX, 0] {
+        match simulate_session_with_stops(&Mousseau, &GameConfig::STANDARD, 100.0, 1.0, 20, stop_loss, seed).exit {
+            SessionExit::Completed => expected.completed += 1,
+            SessionExit::Ruined => expected.ruined += 1,
+            SessionExit::StopWin => expected.stop_win += 1,
+            SessionExit::StopLoss => expected.stop_loss += 1,
+        }
+    }
+    assert_eq!(session_exits(&Mousseau, &GameConfig::STANDARD, 100.0, 1.0, 20, stop_loss, 2, u64::MAX), expected);
+}
+
+#[test]
+fn result_distribution_of_folds() {
+    // A strategy that always folds has one result, the ante lost, on every seat
+    let never = EvThreshold { threshold: f64::INFINITY };
+    assert_eq!(result_distribution(&never, 5, 130), BTreeMap::from([(-1, 5 * NUM_PLAYERS as u64)]));
+}
+
+#[test]
+fn result_accumulator_tracks_counts_and_moments() {
+    // The accumulator keeps one count per distinct result with a running mean and variance
+    let mut accumulator = ResultAccumulator::new();
+    for result in [-1, 3, -1, 1, -1, -4] {
+        accumulator.push(result);
+    }
+    assert_eq!(accumulator.counts(), &BTreeMap::from([(-4, 1), (-1, 3), (1, 1), (3, 1)]));
+    assert_eq!((accumulator.count(), accumulator.min(), accumulator.max()), (6, Some(-4), Some(3)));
+    assert!((accumulator.mean() - -0.5).abs() < 1e-12);
+    assert!((accumulator.variance() - 5.5).abs() < 1e-12);
+    assert_eq!(accumulator.percentiles(&[50.0, 100.0]), vec![-1, 3]);
+    let played = result_accumulator(&Jacobson, 30, 173);
+    assert_eq!(played.counts(), &result_distribution(&Jacobson, 30, 173));
+    assert!(played.counts().len() <= 20 && played.count() == 30 * NUM_PLAYERS as u64);
+}
+
+#[test]
+fn conditional_ev_rejects_impossible_cards() {
+    // Conditional EV replays from its seed and refuses repeated, out-of-deck or too many known cards
+    assert_eq!(conditional_ev(&[50, 46, 41], 5, 151), conditional_ev(&[50, 46, 41], 5, 151));
+    assert!(conditional_ev(&[50, 46, 41], 5, 151).is_ok());
+    assert!(conditional_ev(&[50, 50], 5, 151).is_err());
+    assert!(conditional_ev(&[50, 12], 5, 151).is_err());
+    assert!(conditional_ev(&[60], 5, 151).is_err());
+    assert!(conditional_ev(&[41, 42, 43, 44, 45, 46, 47, 48], 5, 151).is_err());
+    assert!(conditional_ev(&[50], 0, 151).is_err());
+}
+
+#[test]
+fn three_card_ev_by_high_card() {
+    // 3-card flush EV by high card, a smoke test at a few hands per rank
+    let three_card: Vec<f64> = (2..RANKS).map(|high_rank| three_card_ev(high_rank, 3, 164).unwrap()).collect();
+    assert!(three_card.iter().all(|ev| (-4.0..=4.0).contains(ev)));
+    assert!(three_card[RANKS - 3] > three_card[0]); // Ace-high beats 4-high
+    assert_eq!(three_card_ev(7, 3, 164), Ok(three_card[5]));
+}
+
+#[test]
+fn three_card_ev_rejects_empty_runs() {
+    // 3-card flush EV refuses zero iterations and ranks no 3-card flush can be topped by
+    assert!(three_card_ev(7, 0, 164).is_err());
+    assert!(three_card_ev(1, 3, 164).is_err());
+    assert!(three_card_ev(RANKS, 3, 164).is_err());
+}
+
+#[test]
+fn seven_card_flush_never_loses() {
+    // A 7-card flush never loses: +1 when the dealer doesn't qualify, +4 otherwise
+    let seven_card_ev = conditional_ev(&[51, 50, 49, 48, 47, 46, 45], 5, 151).unwrap();
+    assert!((1.0..=4.0).contains(&seven_card_ev));
+}
+
+#[test]
+fn antithetic_pairs_are_negatively_correlated() {
+    // A deal and its role swap have negatively correlated results, so their averages vary less
+    // than averages of independent deals
+    let correlation = |pairs: &[(f64, f64)]| {
+        let n = pairs.len() as f64;
+        let (mean_first, mean_second) = (pairs.iter().map(|p| p.0).sum::<f64>() / n, pairs.iter().map(|p| p.1).sum::<f64>() / n);
+        let covariance: f64 = pairs.iter().map(|p| (p.0 - mean_first) * (p.1 - mean_second)).sum();
+        let spread = |values: Vec<f64>| values.iter().map(|x| x * x).sum::<f64>().sqrt();
+        covariance / (spread(pairs.iter().map(|p| p.0 - mean_first).collect()) * spread(pairs.iter().map(|p| p.1 - mean_second).collect()))
+    };
+    let pair_means = |pairs: &[(f64, f64)]| pairs.iter().map(|(first, second)| (first + second) / 2.0).collect::<Vec<_>>();
+    let antithetic = deal_pairs(&Jacobson, 2000, 158, true);
+    let independent = deal_pairs(&Jacobson, 2000, 158, false);
+    let antithetic_correlation = correlation(&antithetic);
+    assert!(antithetic_correlation < 0.0, "antithetic pair correlation {}", antithetic_correlation);
+    assert!(correlation(&independent).abs() < 0.1);
+    assert!(sample_variance(&pair_means(&antithetic)) < sample_variance(&pair_means(&independent)));
+}
+
+#[test]
+fn antithetic_simulation_averages_whole_pairs() {
+    // The antithetic estimate averages whole pairs, rounding odd counts up and playing at least one
+    let pairs = deal_pairs(&Jacobson, 200, 158, true);
+    let mean = pairs.iter().map(|(first, second)| (first + second) / 2.0).sum::<f64>() / 200.0;
+    assert_eq!(simulate_antithetic(&Jacobson, 400, 158), mean);
+    assert_eq!(simulate_antithetic(&Jacobson, 399, 158), mean);
+    let (first, second) = deal_pairs(&Jacobson, 1, 158, true)[0];
+    assert_eq!(simulate_antithetic(&Jacobson, 0, 158), (first + second) / 2.0);
+}
+
+#[test]
+fn pool_validation_is_reproducible() {
+    // The candidate pool's difference from direct sampling replays from its seed and stays small
+    let pool_discrepancy = validate_pool_method(134, 3);
+    assert_eq!(pool_discrepancy, validate_pool_method(134, 3));
+    assert!(pool_discrepancy.abs() < 3.0);
+}
+
+#[test]
+fn suit_symmetric_flush_counts_match() {
+    // Suit-symmetric flush counts match brute force over a 5-rank deck, and cover every 52-card hand
+    let reduced: [usize; 5 * SUITS] = std::array::from_fn(|card| card);
+    let mut brute = [0u64; HAND_SIZE + 1];
+    visit_combinations(&reduced, 0, &mut [0; HAND_SIZE], 0, &mut |hand: &[usize; HAND_SIZE]| {
+        let mut suits = [0; SUITS];
+        for &card in hand.iter() {
+            suits[card / 5] += 1;
+        }
+        brute[*suits.iter().max().unwrap()] += 1;
+    });
+    assert_eq!(enumerate_flush_categories(5), brute);
+    assert_eq!(enumerate_flush_categories(RANKS).iter().sum::<u64>(), n_choose_k(DECK_SIZE as u64, HAND_SIZE as u64));
+    assert_eq!(suit_orbit_size(&[2, 2, 2, 1]), 4);
+    let flush_lengths = flush_length_probabilities();
+    assert!((flush_lengths.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert_eq!(flush_lengths[0] + flush_lengths[1], 0.0);
+}
+
+#[test]
+fn five_plus_suited_probability() {
+    // 5 or more suited cards is 4 * (C(13,7) + C(13,6) * 39 + C(13,5) * C(39,2)) hands, about 3.06%
+    let flush_lengths = flush_length_probabilities();
+    assert_eq!(&enumerate_flush_categories(RANKS)[5..], &[3814668, 267696, 6864]);
+    assert!((flush_lengths[5..].iter().sum::<f64>() - 0.030566).abs() < 1e-6);
+}
+
+#[test]
+fn flush_categories_cover_every_hand() {
+    // Flush categories cover every 7-card hand and carry the standard play bet
+    let categories = flush_category_probabilities();
+    assert!((categories.iter().map(|&(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-12);
+    assert_eq!(FlushCategory::from_len(5), Some(FlushCategory::FiveCard));
+    assert_eq!(FlushCategory::from_len(get_best_flush(&[39, 40, 41, 42, 51, 5, 18]).len()), Some(FlushCategory::FiveCard));
+    assert_eq!(FlushCategory::from_len(1), None);
+    assert_eq!(FlushCategory::from_len(HAND_SIZE + 1), None);
+    let mut counts = FlushCategoryCounts::default();
+    assert_eq!(counts.record(7), Some(FlushCategory::SevenCard));
+    assert_eq!(counts.record(9), None);
+    assert_eq!((counts.count(FlushCategory::SevenCard), counts.total()), (1, 1));
+    assert_eq!(FlushCategory::FiveCard.to_string(), "5-card flush");
+    assert_eq!(FlushCategory::ALL.map(|category| category.play_multiplier(&GameConfig::STANDARD)), [1, 1, 1, 2, 3, 3]);
+}
+
+#[test]
+fn importance_sampling_agrees_with_uniform() {
+    // Importance sampling estimates the same edge as uniform deals, within four combined standard errors
+    let uniform = simulate_strategy_with(&Jacobson, &GameConfig::STANDARD, 5000, 132);
+    let samples = importance_samples(&Jacobson, 15000, 132, 2.0);
+    let weighted = samples.iter().sum::<f64>() / samples.len() as f64;
+    assert_eq!(simulate_importance(&Jacobson, 15000, 132, 2.0), weighted);
+    let weighted_err = (sample_variance(&samples) / samples.len() as f64).sqrt();
+    let tolerance = 4.0 * (uniform.std_err().powi(2) + weighted_err.powi(2)).sqrt();
+    assert!(tolerance < 0.05, "tolerance {} is too loose to catch a biased estimate", tolerance);
+    assert!((uniform.per_hand() - weighted).abs() < tolerance, "importance sampling {} vs uniform {} ± {}", weighted, uniform.per_hand(), tolerance);
+}
+
+#[test]
+fn result_percentiles_pick_sorted_results() {
+    // Percentiles walk the sorted result counts, and an empty distribution has none
+    let spread = BTreeMap::from([(-1, 50), (1, 40), (3, 10)]);
+    assert_eq!(result_percentiles(&spread, &[0.0, 1.0, 50.0, 51.0, 95.0, 100.0]), vec![-1, -1, -1, 1, 3, 3]);
+    assert!(result_percentiles(&BTreeMap::new(), &[50.0]).is_empty());
+    assert!(result_percentiles(&BTreeMap::from([(1, 0)]), &[50.0]).is_empty());
+}
+
+#[test]
+fn direct_sampling_of_folds_matches_pool() {
+    // A strategy that always folds loses the same whichever way the dealer's hand is drawn
+    let never = EvThreshold { threshold: f64::INFINITY };
+    assert_eq!(simulate_direct(&never, 20, 128), simulate_strategy_seeded(&never, 20, 128));
+}
+
+#[test]
+fn raising_fold_cost_shifts_edge() {
+    // Raising the fold cost shifts the edge by the extra cost times the fold rate
+    let costly_folds = GameConfig { fold_cost: 1.5, ..GameConfig::STANDARD };
+    let standard = run_strategy(&Mousseau, &GameConfig::STANDARD, 200, DealerDraw::CandidatePool, &mut ChaCha8Rng::seed_from_u64(117));
+    let costly = run_strategy(&Mousseau, &costly_folds, 200, DealerDraw::CandidatePool, &mut ChaCha8Rng::seed_from_u64(117));
+    let fold_rate = standard.folds as f64 / standard.hands as f64;
+    assert_eq!(standard.folds, costly.folds);
+    assert!((standard.per_hand() - costly.per_hand() - 0.5 * fold_rate).abs() < 1e-9);
+}
+
+#[test]
+fn averages_share_definitions() {
+    // Averages use one definition of hands and units
+    let mut totals = SimulationResult::new();
+    totals.record(Decision::Fold, -1.0);
+    totals.record(Decision::Play(2), 3.0);
+    assert_eq!(totals.units, 4.0);
+    assert_eq!(totals.per_hand(), 1.0);
+    assert_eq!(totals.per_unit(), totals.net / totals.units);
+    assert_eq!(totals.avg_raise_multiplier(), 2.0);
+    assert_eq!(totals.hourly(25.0, 30.0), 750.0);
+    let mut merged = SimulationResult::new();
+    merged.merge(&totals);
+    merged.merge(&totals);
+    assert_eq!((merged.hands, merged.folds, merged.per_hand()), (4, 2, totals.per_hand()));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn stream_reports_progress_in_batches() {
+    // The progress stream reports running deal counts once per batch and once at the end
+    use tokio_stream::StreamExt;
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let updates: Vec<ProgressUpdate> = runtime.block_on(async {
+        let mut stream = simulate_stream(Mousseau, GameConfig::STANDARD, 25, 149, 10);
+        let mut updates = Vec::new();
+        while let Some(update) = stream.next().await {
+            updates.push(update);
+        }
+        updates
+    });
+    assert_eq!(updates.iter().map(|update| update.deals).collect::<Vec<usize>>(), vec![10, 20, 25]);
+}
+
+#[test]
+fn outcome_counts_track_dealer_hand() {
+    // Outcome counts track every dealer hand a played hand meets
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let dealer_low = [0, 1, 2, 15, 16, 30, 40]; // 3-card diamond flush, too low
+    assert_eq!(classify_hands(&player, &dealer, &GameConfig::STANDARD), HandClass::Win);
+    assert_eq!(classify_hands(&dealer, &player, &GameConfig::STANDARD), HandClass::Loss);
+    assert_eq!(classify_hands(&player, &dealer_low, &GameConfig::STANDARD), HandClass::DealerNotQualified);
+    let result = simulate_strategy_with(&Mousseau, &GameConfig::STANDARD, 100, 120);
+    assert_eq!(result.outcomes.total(), (result.hands - result.folds) as u64 * DEALER_COMBINATIONS);
+    assert!(result.outcomes.win_rate() > 0.0 && result.outcomes.win_rate() < 1.0);
+}
+
+#[test]
+fn joker_extends_4_card_flush() {
+    // A joker extends a 4-card flush to 5 as its highest missing card
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let joker_hand = [39, 40, 41, 42, WILD_CARD, 5, 18];
+    assert_eq!(card_name(WILD_CARD), "Jo");
+    assert_eq!(card_name(99), "??");
+    let mut suit_counts = [0; SUITS];
+    add_suit_counts(&mut suit_counts, &joker_hand);
+    assert_eq!(suit_counts, [1, 1, 0, 4]);
+    assert_eq!(parse_card_or_joker("Jo"), Ok(WILD_CARD));
+    assert_eq!(parse_card("Jo"), Err(ParseCardError::BadSuit('o')));
+    assert_eq!(&get_best_flush_wild(&joker_hand)[..], &[51, 42, 41, 40, 39]);
+    assert_eq!(get_best_flush_wild(&player), get_best_flush_masked(hand_mask(&player)));
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let wild_rules = GameConfig { wild_cards: true, ..GameConfig::STANDARD };
+    assert_eq!(compare_hands_with(&joker_hand, &dealer, &wild_rules), 3);
+}
+
+#[test]
+fn non_qualifying_dealer_pays_ante() {
+    // A non-qualifying dealer pays the ante, or pushes under the variant rule
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let dealer_low = [0, 1, 2, 15, 16, 30, 40]; // 3-card diamond flush, too low
+    let non_qualify_push = GameConfig { non_qualify: NonQualifyRule::Push, ..GameConfig::STANDARD };
+    assert_eq!(compare_hands_with(&player, &dealer_low, &GameConfig::STANDARD), 1);
+    assert_eq!(compare_hands_with(&player, &dealer_low, &non_qualify_push), 0);
+    assert_eq!(classify_hands(&player, &dealer_low, &non_qualify_push), HandClass::DealerNotQualified);
+    assert_eq!(
+        compare_hands_detailed(&player, &dealer_low, &non_qualify_push).outcome(),
+        Outcome::DealerNotQualified { ante: 0 }
+    );
+    // A win with the play bet capped to nothing is still a win, not a non-qualifying dealer
+    let no_raise = GameConfig { max_raise: Some(0), ..GameConfig::STANDARD };
+    let capped_win = compare_hands_detailed(&player, &dealer, &no_raise);
+    assert_eq!((capped_win.class(), capped_win.outcome()), (HandClass::Win, Outcome::PlayerWins { ante: 1, play: 0 }));
+    assert_eq!(compare_hands_with(&player, &dealer, &non_qualify_push), compare_hands(&player, &dealer).net_antes());
+}
+
+#[test]
+fn flat_raise_pays_1x_on_seven_card_win() {
+    // Never raising past 1x turns a 7-card flush win into ante + 1
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let seven_flush = [39, 40, 41, 42, 43, 44, 51];
+    assert_eq!(compare_hands(&seven_flush, &dealer), Outcome::PlayerWins { ante: 1, play: 3 });
+    assert_eq!(compare_hands_with(&seven_flush, &dealer, &GameConfig::STANDARD.flat_raise()), 2);
+}
+
+#[test]
+fn raise_cap_cuts_seven_card_win() {
+    // A 2x cap takes one unit off a winning 7-card flush and counts every hand it cuts
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let seven_flush = [39, 40, 41, 42, 43, 44, 51];
+    let capped = GameConfig::STANDARD.capped_raise(2).unwrap();
+    assert!(GameConfig::STANDARD.capped_raise(0).is_err());
+    assert!(GameConfig::STANDARD.capped_raise(-1).is_err());
+    assert_eq!(compare_hands_with(&seven_flush, &dealer, &capped), 3);
+    assert_eq!((capped.play_multiplier(7), capped.raise_capped(7), capped.raise_capped(5)), (2, true, false));
+    assert!(compare_hands_with(&seven_flush, &dealer, &capped) < compare_hands_with(&seven_flush, &dealer, &GameConfig::STANDARD));
+    // A reversed deck seats 7-card spade, heart and club flushes over the dealer's 7-card diamond
+    // flush, and a 6-card and a 5-card flush under it
+    let mut deck = Deck::ordered();
+    deck.cards.reverse();
+    let round = deck.deal_round();
+    let (mut uncapped_run, mut capped_run) = (SimulationResult::new(), SimulationResult::new());
+    play_round(&PlayAll, &GameConfig::STANDARD, DealerDraw::CandidatePool, round.player_hands(), round.dealer_cards(), &mut uncapped_run);
+    play_round(&PlayAll, &capped, DealerDraw::CandidatePool, round.player_hands(), round.dealer_cards(), &mut capped_run);
+    assert_eq!((uncapped_run.net, capped_run.net), (3.0, 1.0));
+    assert_eq!((uncapped_run.capped_raises, capped_run.capped_raises), (0, 4));
+    assert_eq!(capped_run.capped_raises, uncapped_run.play_multipliers - capped_run.play_multipliers);
+    // The Mousseau driver records the capped play bet and counts the hands it cut
+    let mousseau_capped = simulate_mousseau_strategy(1000, &capped);
+    assert!(mousseau_capped.capped_raises > 0);
+    assert!(mousseau_capped.avg_raise_multiplier() <= 2.0);
+    assert_eq!(mousseau_capped.units, (mousseau_capped.hands + mousseau_capped.play_multipliers as usize) as f64);
+}
+
+#[test]
+fn flush_bonus_side_bet() {
+    // The Flush Bonus pays on the player's flush alone and is tallied apart from the main game
+    let paytable = FlushBonusPaytable::default();
+    assert_eq!(flush_bonus_payout(&[39, 40, 41, 42, 43, 44, 51], &paytable), 300);
+    assert_eq!(flush_bonus_payout(&[0, 1, 2, 15, 16, 30, 40], &paytable), -1);
+    assert_eq!(FlushBonusPaytable::try_from("4:1 5:10 6:100 7:300").unwrap(), paytable);
+    // Odds of 0 push the bet, and a joker extends the flush when wild cards are in play
+    let with_push = FlushBonusPaytable::try_from("3:0 4:1 5:10 6:100 7:300").unwrap();
+    assert_eq!(with_push.odds[3], Some(0));
+    assert_eq!(flush_bonus_payout(&[0, 1, 2, 15, 16, 30, 40], &with_push), 0);
+    let joker_hand = [39, 40, 41, 42, WILD_CARD, 5, 18];
+    assert_eq!(flush_bonus_payout_wild(&joker_hand, &paytable), 10);
+    assert_eq!(flush_bonus_payout(&joker_hand, &paytable), 1);
+    assert!(FlushBonusPaytable::try_from("8:1").is_err());
+    assert!(FlushBonusPaytable::try_from("4-1").is_err());
+    let expected: f64 = flush_length_probabilities()
+        .iter()
+        .zip(paytable.odds)
+        .map(|(p, odds)| p * odds.map_or(-1.0, f64::from))
+        .sum();
+    assert!(expected < 0.0 && expected > -0.1);
+    let plain = simulate_strategy_with(&Mousseau, &GameConfig::STANDARD, 200, 258);
+    let with_bonus = simulate_strategy_with(&Mousseau, &GameConfig::STANDARD.with_flush_bonus(paytable), 200, 258);
+    assert_eq!((plain.flush_bonus_bets, plain.flush_bonus_per_bet()), (0, 0.0));
+    assert_eq!(with_bonus.flush_bonus_bets, with_bonus.hands as u64);
+    assert_eq!((with_bonus.net, with_bonus.units), (plain.net, plain.units));
+    assert_eq!(
+        with_bonus.combined_per_unit(),
+        (with_bonus.net + with_bonus.flush_bonus_net) / (with_bonus.units + with_bonus.flush_bonus_bets as f64)
+    );
+}
+
+#[test]
+fn raising_qualify_cutoff_stops_low_dealer() {
+    // Raising the cutoff to Jack stops the dealer's 3-card T-9-8 from qualifying
+    let dealer_low = [0, 1, 2, 15, 16, 30, 40]; // 3-card diamond flush, too low
+    let queen_high = [34, 33, 32, 3, 17, 44, 8];
+    assert_eq!(compare_hands(&dealer_low, &queen_high), Outcome::DealerWins { ante: 1, play: 1 });
+    assert_eq!(compare_hands(&dealer_low, &queen_high).net_antes(), -2);
+    assert_eq!(compare_hands_with(&dealer_low, &queen_high, &GameConfig { qualify_rank: 9, ..GameConfig::STANDARD }), 1);
+}
+
+#[test]
+fn qualify_sensitivity_reports_each_cutoff() {
+    // The sensitivity sweep reports one house edge per 3-card cutoff, the standard one Mousseau's
+    let sensitivity = qualify_sensitivity(20, 140);
+    assert_eq!(sensitivity.len(), RANKS - 1);
+    assert_eq!(sensitivity[5], (7, -simulate_strategy_seeded(&Mousseau, 20, 140)));
+}
+
+#[test]
+fn dealer_folding_short_flushes_concedes() {
+    // A dealer who folds short flushes concedes a hand their qualifying A-K-Q would have won
+    let dealer = [26, 27, 28, 29, 4, 17, 30]; // 5-card heart flush
+    let dealer_low = [0, 1, 2, 15, 16, 30, 40]; // 3-card diamond flush, too low
+    let dealer_three_flush = [36, 37, 38, 3, 17, 44, 8];
+    assert_eq!(compare_hands(&dealer_low, &dealer_three_flush).net_antes(), -2);
+    let folding_dealer = GameConfig { dealer_plays: Some(dealer_plays_four_flush), ..GameConfig::STANDARD };
+    assert_eq!(compare_hands_with(&dealer_low, &dealer_three_flush, &folding_dealer), 1);
+    assert_eq!(compare_hands_with(&dealer_low, &dealer, &folding_dealer), -2);
+}
+
+#[test]
+fn equal_flushes_compare_kickers() {
+    // Equal 9-7-5-3 flushes: diamonds with A-2-2 kickers against spades with K-3-3
+    let tied_player = [7, 5, 3, 1, 25, 26, 39];
+    let tied_dealer = [46, 44, 42, 40, 24, 27, 14];
+    assert_eq!(compare_hands(&tied_player, &tied_dealer), Outcome::Push);
+    let suit_rank = GameConfig { tie_break: TieBreak::SuitRank, ..GameConfig::STANDARD };
+    assert_eq!(compare_hands_with(&tied_player, &tied_dealer, &suit_rank), -2);
+    assert_eq!(compare_hands_with(&tied_dealer, &tied_player, &suit_rank), 2);
+    let kickers = GameConfig { tie_break: TieBreak::HighestKicker, ..GameConfig::STANDARD };
+    assert_eq!(compare_hands_with(&tied_player, &tied_dealer, &kickers), 2);
+    assert_eq!(compare_hands_with(&tied_dealer, &tied_player, &kickers), -2);
+}
+
+#[test]
+fn progressive_meter_grows_each_hand() {
+    // Progressive meter grows each hand and resets when a 7-card straight flush takes it
+    let straight = [46, 47, 48, 49, 50, 5, 18]; // 9-K spade straight flush
+    let gapped = [39, 41, 43, 45, 47, 5, 18]; // 5-card spade flush, no straight
+    let mut progressive = ProgressiveBet::new(100.0, 0.5, [0.0, 0.0, 0.0, 5.0, 40.0, 100.0, 500.0, 0.0]);
+    assert_eq!(progressive.play(&gapped), ProgressivePayout { paid: 0.0, jackpot: false });
+    assert_eq!(progressive.meter, 100.5);
+    assert_eq!(progressive.play(&straight).paid, 100.0);
+    assert_eq!(progressive.play(&[39, 40, 41, 42, 43, 44, 45]), ProgressivePayout { paid: 101.5, jackpot: true });
+    assert_eq!(progressive.meter, 100.0);
+    // A straight flush in the shorter suit pays even when another suit holds the longer flush
+    let short_suit = [39, 41, 43, 45, 0, 1, 2]; // 4-card spade flush, 2-3-4 diamond straight flush
+    assert_eq!(progressive.play(&short_suit).paid, 5.0);
+    // A jackpot is still counted when nothing is contributed and the meter sits at its seed
+    let mut flat = ProgressiveBet::new(100.0, 0.0, [0.0; HAND_SIZE + 1]);
+    assert_eq!(flat.play(&[39, 40, 41, 42, 43, 44, 45]), ProgressivePayout { paid: 100.0, jackpot: true });
+    assert_eq!(flat.meter, 100.0);
+    let mut progressive = ProgressiveBet::default();
+    let session = simulate_progressive(&mut progressive, 200, 126);
+    assert_eq!(session.hands, 1200);
+    assert!((session.final_meter - (10000.0 + 0.1 * 1200.0)).abs() < 1e-6 || session.jackpots > 0);
+    assert!((session.long_run_rtp() - (session.rtp() + 0.1)).abs() < 1e-9 || session.jackpots > 0);
+}
+
+#[test]
+fn dealer_average_without_flush() {
+    // Test dealer average with less than 3-card flush
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let dealer_no_flush = [0, 1, 2, 13, 14, 15, 26, 27, 28, 39]; // Not qualified
+    let result3 = calculate_average_result(&player, &dealer_no_flush);
+    assert_eq!(result3, 1.0);
+}
+
+#[test]
+fn paired_seats_sharing_suit_leave_bet_to_stronger() {
+    // Paired seats sharing a suit leave the bet to the stronger flush
+    let pair_table = |seat_0: [usize; HAND_SIZE], seat_1: [usize; HAND_SIZE]| {
+        let front: Vec<usize> = seat_0.iter().chain(seat_1.iter()).copied().collect();
+        let mut cards = front.clone();
+        cards.extend((0..DECK_SIZE).filter(|card| !front.contains(card)));
+        let round = Deck::try_from_cards(cards.try_into().unwrap()).unwrap().deal_round();
+        let mut context = RoundContext::new(round.player_hands(), round.dealer_cards(), &GameConfig::STANDARD);
+        let seat_0_plays = SuitSharingPairs.plays(round.player(0), &context);
+        context.reveal(round.player(0));
+        (seat_0_plays, SuitSharingPairs.plays(round.player(1), &context))
+    };
+    let spades_akqj = [51, 50, 49, 48, 0, 13, 26];
+    assert_eq!(pair_table(spades_akqj, [47, 46, 45, 44, 1, 14, 27]), (true, false)); // Both in spades, T-9-8-7 folds
+    assert_eq!(pair_table(spades_akqj, [34, 33, 32, 31, 1, 14, 47]), (true, true)); // T-9-8-7 of hearts plays
+}
+
+#[test]
+fn suit_sharing_pairs_between_mousseau_and_jacobson() {
+    // Over many deals sharing a suit with one partner beats playing every hand and Mousseau, but not
+    // the whole table colluding
+    let (over_play_all, p_play_all) = paired_significance(&SuitSharingPairs, &PlayAll, 500, 172);
+    assert!(over_play_all > 0.0 && p_play_all < 0.001, "{} over Always play, p = {}", over_play_all, p_play_all);
+    let (over_mousseau, p_mousseau) = paired_significance(&SuitSharingPairs, &Mousseau, 500, 172);
+    assert!(over_mousseau > 0.0 && p_mousseau < 0.001, "{} over Mousseau, p = {}", over_mousseau, p_mousseau);
+    let (under_jacobson, p_jacobson) = paired_significance(&Jacobson, &SuitSharingPairs, 500, 172);
+    assert!(under_jacobson > 0.0 && p_jacobson < 0.001, "{} under Jacobson, p = {}", under_jacobson, p_jacobson);
+}
+
+#[test]
+fn clairvoyant_edge_is_ceiling() {
+    // Perfect hindsight is a ceiling for every strategy on the same deals
+    let clairvoyant = clairvoyant_edge(100, 178);
+    for (name, strategy) in named_strategies() {
+        assert!(clairvoyant >= simulate_direct(strategy, 100, 178), "{} beat the clairvoyant edge", name);
+    }
+}
+
+#[test]
+fn ev_threshold_strategy_reads_dealer() {
+    // EV threshold strategy reads the dealer pool from the round context
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let table = Deck::ordered().get_player_hands();
+    let dealer_no_flush = [0, 1, 2, 13, 14, 15, 26, 27, 28, 39]; // Not qualified
+    let no_flush_context = RoundContext::new(&table, &dealer_no_flush, &GameConfig::STANDARD);
+    assert!(EvThreshold::default().plays(&player, &no_flush_context)); // Average is exactly 1.0
+    assert!(!EvThreshold { threshold: 1.0 }.plays(&player, &no_flush_context));
+}
+
+#[test]
+fn ev_threshold_reads_rules_from_context() {
+    // EV threshold strategy weighs the dealer pool under the context's rules, not the standard game's
+    let player = [39, 40, 41, 42, 51, 5, 18]; // 5-card spade flush
+    let table = Deck::ordered().get_player_hands();
+    let dealer_no_flush = [0, 1, 2, 13, 14, 15, 26, 27, 28, 39]; // Not qualified
+    let non_qualify_push = GameConfig { non_qualify: NonQualifyRule::Push, ..GameConfig::STANDARD };
+    let half = EvThreshold { threshold: 0.5 };
+    assert!(half.plays(&player, &RoundContext::new(&table, &dealer_no_flush, &GameConfig::STANDARD)));
+    assert!(!half.plays(&player, &RoundContext::new(&table, &dealer_no_flush, &non_qualify_push))); // Every hand pushes
+}
+
+#[test]
+fn csv_fixtures_report_each_row() {
+    // CSV fixtures report each row's result
+    let fixture = "player_cards,dealer_cards,expected_result\n\
+        2s 3s 4s 5s As 7d 7c,2h 3h 4h 5h 6d 6c 6h,3\n\
+        2s 3s 4s 5s As 7d 7c,2d 3d 4d 4c 5c 6h 3h,2\n";
+    let results = run_fixture_reader(fixture.as_bytes()).unwrap();
+    assert!(results[0].passed());
+    assert!(!results[1].passed()); // Dealer doesn't qualify, pays 1
+    assert_eq!(results[1].row, 3);
+    assert!(run_fixture_reader("header\nAs Ks,2d,1\n".as_bytes()).is_err());
+    // A row whose hands share a card can't come from a real deal
+    let overlapping = "player_cards,dealer_cards,expected_result\n\
+        2s 3s 4s 5s As 7d 7c,2h 3h 4h 5h 6d 6c 6h,3\n\
+        2s 3s 4s 5s As 7d 7c,2d 3d 4d 4c 5c 6h 3s,1\n";
+    assert_eq!(run_fixture_reader(overlapping.as_bytes()).err().unwrap().to_string(), "row 3: 3s was dealt more than once");
+    assert!(run_fixture(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/hands.csv")).unwrap().iter().all(FixtureResult::passed));
+}
+
+#[test]
+fn player_count_sweep_with_every_seat_is_jacobson() {
+    // With every seat colluding the player count sweep is plain Jacobson
+    let by_players = collusion_by_player_count(NUM_PLAYERS, 100, 160);
+    assert_eq!(by_players.iter().map(|&(players, _)| players).collect::<Vec<usize>>(), vec![2, 3, 4, 5, 6]);
+    assert!((by_players[4].1 - simulate_strategy_seeded(&Jacobson, 100, 160)).abs() < 1e-9);
+}
+
+#[test]
+fn player_count_sweep_weighs_every_dealer_pool() {
+    // Five colluders leave 17 cards unseen. Trying every 10 of them as the dealer's pool, each colluder
+    // plays when the Jacobson table plays its flush for at least half of those pools
+    let unseen_cards = 17;
+    let five_seats = collusion_by_player_count(5, 20, 160)[3];
+    let mut rng = ChaCha8Rng::seed_from_u64(160);
+    let mut deck = Deck::ordered();
+    let mut total = 0.0;
+    for _ in 0..20 {
+        deck.shuffle_with(&mut rng);
+        let (hands, dealer_cards) = (deck.get_player_hands(), deck.get_dealer_cards());
+        let unseen = &deck.cards[DECK_SIZE - unseen_cards..];
+        let mut pools_by_code = [0u64; RANKS];
+        for pool in (0..1u32 << unseen_cards).filter(|pool| pool.count_ones() as usize == DEALER_CARDS) {
+            let mut signals = [0; SUITS];
+            add_suit_counts(&mut signals, (0..unseen_cards).filter(|i| pool & 1 << i != 0).map(|i| &unseen[i]));
+            signals.sort();
+            pools_by_code[get_strategy(signals)] += 1;
+        }
+        let pools: u64 = pools_by_code.iter().sum();
+        let seat_results = table_results(&hands, &dealer_cards);
+        for (hand, result) in hands[..5].iter().zip(seat_results) {
+            let playing: u64 = (0..RANKS).filter(|&code| should_play(get_best_flush(hand), code)).map(|code| pools_by_code[code]).sum();
+            total += if 2 * playing >= pools { result } else { -1.0 };
+        }
+    }
+    assert_eq!(five_seats.0, 5);
+    assert!((five_seats.1 - total / 100.0).abs() < 1e-9, "sweep {} against brute force {}", five_seats.1, total / 100.0);
+}
+
+#[test]
+fn run_reports_render_same_fields() {
+    // Run reports render the same fields in each output format
+    let report = RunReport { strategy: "Say \"hi\", x".to_string(), iterations: 10, seed: 162, edge: -0.5, std_err: 0.25 };
+    assert_eq!(report.render(OutputFormat::Json), r#"{"strategy":"Say \"hi\", x","iterations":10,"seed":162,"edge":-0.5,"std_err":0.25}"#);
+    assert_eq!(report.render(OutputFormat::Csv), r#""Say ""hi"", x",10,162,-0.5,0.25"#);
+    let mut spread_result = SimulationResult::new();
+    for net in [-1.0, 1.0, -1.0, 1.0] {
+        spread_result.record(Decision::Play(1), net);
+    }
+    assert_eq!(spread_result.std_err(), (4.0f64 / 3.0 / 4.0).sqrt());
+}
+
+#[test]
+fn decision_tree_covers_every_dealer_split() {
+    // The decision tree covers every dealer suit split once with a code should_play() knows
+    let tree = strategy_decision_tree();
+    assert_eq!(tree.len(), dealer_suit_splits().len());
+    assert!(tree.iter().all(|&(s0, s1, s2, s3, code)| s0 + s1 + s2 + s3 == DEALER_CARDS && (4..=12).contains(&code)));
+    assert!(tree.contains(&(0, 0, 2, 8, 5)) && tree.contains(&(2, 2, 3, 3, 12)));
+}
+
+#[test]
+fn checked_lookups_accept_real_splits() {
+    // Checked lookups accept every real split and reject the rest, and no code makes should_play() panic
+    assert!(dealer_suit_splits().into_iter().all(|signals| try_get_strategy(signals) == Ok(get_strategy(signals))));
+    assert_eq!(try_get_strategy([0, 0, 0, 10]), Ok(7));
+    assert_eq!(try_get_strategy([3, 3, 3, 1]), Err(StrategyError::UnsortedSignals([3, 3, 3, 1])));
+    assert_eq!(try_get_strategy([3, 3, 3, 3]), Err(StrategyError::WrongSignalTotal([3, 3, 3, 3])));
+    assert_eq!(get_strategy([3, 3, 3, 3]), 12);
+    let three_flush = get_best_flush(&[0, 2, 4, 13, 15, 26, 28]);
+    assert!((0..=3).all(|code| should_play(three_flush, code)));
+    assert!(!should_play(three_flush, 4) && should_play(three_flush, 13));
+}
+
+#[test]
+fn per_iteration_seeding_matches_across_threads() {
+    // Per-iteration seeding gives the same parallel result on any number of threads
+    assert_eq!(Deck::new_for_iteration(169, 7).get_cards(), Deck::new_for_iteration(169, 7).get_cards());
+    assert_ne!(Deck::new_for_iteration(169, 7).get_cards(), Deck::new_for_iteration(169, 8).get_cards());
+    assert_ne!(Deck::new_for_iteration(169, 7).get_cards(), Deck::new_for_iteration(170, 7).get_cards());
+    let single_thread = simulate_parallel(&Jacobson, &GameConfig::STANDARD, 1200, 169, 1);
+    for threads in [2, 3, 16] {
+        let multi_thread = simulate_parallel(&Jacobson, &GameConfig::STANDARD, 1200, 169, threads);
+        assert_eq!((multi_thread.hands, multi_thread.folds), (single_thread.hands, single_thread.folds));
+        assert_eq!((multi_thread.net, multi_thread.units), (single_thread.net, single_thread.units));
+    }
+    assert_eq!(single_thread.hands, 1200 * NUM_PLAYERS);
+}
+
+#[test]
+fn precision_gated_runs_stop_at_precision_or_cap() {
+    // Precision-gated runs stop at the first batch that is precise enough, or at the cap
+    let (loose, loose_deals) = simulate_until(&Jacobson, 10.0, 5000, 171);
+    assert_eq!(loose_deals, PRECISION_BATCH);
+    assert_eq!(loose.hands, loose_deals * NUM_PLAYERS);
+    assert!(loose.confidence_half_width() <= 10.0);
+    let (capped, capped_deals) = simulate_until(&Jacobson, 0.0, 1500, 171);
+    assert_eq!(capped_deals, 1500);
+    assert_eq!(capped.hands, capped_deals * NUM_PLAYERS);
+    assert_eq!(capped.net, simulate_until(&Jacobson, 0.0, 1500, 171).0.net);
+}
+
+#[test]
+fn mixed_table_reports_each_seat() {
+    // A mixed table reports each seat, and a table of one strategy matches the single-strategy run
+    let mixed_seats: [Box<dyn Strategy>; NUM_PLAYERS] =
+        std::array::from_fn(|seat| if seat < 3 { Box::new(Jacobson) as Box<dyn Strategy> } else { Box::new(Mousseau) });
+    let mixed = simulate_mixed_table(&mixed_seats, &GameConfig::STANDARD, 40, 176);
+    assert!(mixed.iter().all(|seat| seat.hands == 40));
+    let jacobson_seats = combine_seats(&mixed, &[0, 1, 2]);
+    let uniform = simulate_strategy_with(&Jacobson, &GameConfig::STANDARD, 40, 176);
+    let uniform_seats = simulate_mixed_table(&std::array::from_fn(|_| Box::new(Jacobson) as Box<dyn Strategy>), &GameConfig::STANDARD, 40, 176);
+    assert_eq!(combine_seats(&uniform_seats, &[0, 1, 2, 3, 4, 5]).hands, uniform.hands);
+    assert!((combine_seats(&uniform_seats, &[0, 1, 2, 3, 4, 5]).net - uniform.net).abs() < 1e-9);
+    for seat in 0..3 {
+        assert_eq!(mixed[seat].net, uniform_seats[seat].net); // The Jacobson seats don't depend on their neighbours
+    }
+    assert_eq!(jacobson_seats.hands, 120);
+}
+
+#[test]
+fn decision_tables_round_trip_through_csv() {
+    // Decision tables round trip through CSV and the built-in one plays exactly like Jacobson
+    let builtin = TableStrategy::default();
+    let table_csv = builtin.to_csv();
+    assert_eq!(TableStrategy::from_csv_reader(table_csv.as_bytes()).unwrap(), builtin);
+    assert!(table_csv.contains("signal,0 0 2 8,5\n") && table_csv.contains("rule,9,3 J\n"));
+    assert_eq!(simulate_strategy_seeded(&builtin, 200, 159), simulate_strategy_seeded(&Jacobson, 200, 159));
+    let missing_signal = table_csv.replace("signal,0 0 2 8,5\n", "");
+    assert!(TableStrategy::from_csv_reader(missing_signal.as_bytes()).is_err());
+    let missing_rule = table_csv.replace("rule,9,3 J\n", "");
+    assert!(TableStrategy::from_csv_reader(missing_rule.as_bytes()).is_err());
+    assert!(TableStrategy::from_csv_reader(format!("{}signal,0 0 2 8,5\n", table_csv).as_bytes()).is_err());
+    let bad_rank = table_csv.replace("rule,9,3 J\n", "rule,9,3 Jd\n");
+    assert!(TableStrategy::from_csv_reader(bad_rank.as_bytes()).is_err());
+    assert_eq!(builtin.strategy(&[0, 0, 2, 8]), Ok(5));
+    assert_eq!(builtin.strategy(&[0, 0, 1, 8]), Err(StrategyError::UnknownSignals([0, 0, 1, 8])));
+}
+
+#[test]
+fn play_multipliers_follow_hand_size() {
+    // Play multipliers follow the hand size
+    assert_eq!(&GameConfig::STANDARD.play_multipliers[..8], &[1, 1, 1, 1, 1, 2, 3, 3]);
+    let five_card = GameConfig::for_hand_size(5);
+    assert_eq!(&five_card.play_multipliers[..6], &[1, 1, 1, 2, 3, 3]);
+    assert_eq!(compare_hands_with(&[39, 40, 41, 42, 0], &[36, 37, 38, 1, 14], &five_card), 4);
+    assert_eq!(mousseau_strategy_gated(&[39, 40, 41, 42, 0], MOUSSEAU_GATE, &five_card), 3);
+    assert_eq!(mousseau_strategy_with(&[39, 40, 41, 42, 0], &five_card), 3);
+    assert_eq!(mousseau_strategy(&[39, 40, 41, 42, 0]), 1);
+    assert_eq!(mousseau_strategy(&[39, 40, 41, 42, 51, 5, 18]), 2);
+}
+
+#[test]
+fn two_card_qualifier() {
+    // A 2-card qualifier lets the dealer stay in with a 2-card flush the standard game rejects
+    let five_card = GameConfig::for_hand_size(5);
+    let two_card = five_card.two_card_qualifier(7);
+    let dealer_two = [12, 11, 13, 26, 39]; // A-K of diamonds
+    let player_three = [40, 41, 42, 14, 27]; // 3-4-5 of spades
+    assert_eq!(get_best_flush(&dealer_two).len(), 2);
+    assert!(!dealer_qualifies(&get_best_flush(&dealer_two), &five_card));
+    assert!(dealer_qualifies(&get_best_flush(&dealer_two), &two_card));
+    assert!(!dealer_qualifies(&get_best_flush(&[5, 4, 13, 26, 39]), &two_card)); // 7-high is too low
+    assert_eq!(compare_hands_with(&player_three, &dealer_two, &five_card), 1);
+    assert_eq!(compare_hands_with(&player_three, &dealer_two, &two_card), 3);
+    assert_eq!(mousseau_strategy_gated(&player_three, MOUSSEAU_GATE, &two_card), 2); // Beats the qualifier
+    assert_eq!(mousseau_strategy_gated(&dealer_two, MOUSSEAU_GATE, &two_card), 1);
+    assert_eq!(mousseau_strategy_gated(&[5, 4, 13, 26, 39], MOUSSEAU_GATE, &two_card), 0);
+}
+
+#[test]
+fn mousseau_seats_read_rules_from_context() {
+    // The Mousseau seat strategies read the table's rules from the RoundContext
+    let two_two_two = [12, 11, 14, 15, 27, 28, 40]; // A-K of diamonds over 3-4 of clubs and hearts
+    let table = [two_two_two; NUM_PLAYERS];
+    let dealer_cards = Deck::ordered().get_dealer_cards();
+    let standard = RoundContext::new(&table, &dealer_cards, &GameConfig::STANDARD);
+    let seven_card_two = RoundContext::new(&table, &dealer_cards, &GameConfig::STANDARD.two_card_qualifier(7));
+    assert!(!Mousseau.plays(&two_two_two, &standard));
+    assert!(Mousseau.plays(&two_two_two, &seven_card_two));
+    assert!(GatedMousseau { gate: MOUSSEAU_GATE }.plays(&two_two_two, &seven_card_two));
+    assert!(!GatedMousseau { gate: (12, 12, 0) }.plays(&two_two_two, &seven_card_two));
+}
+
+#[test]
+fn combination_counts() {
+    // Combination counts
+    assert_eq!(n_choose_k(5, 0), 1);
+    assert_eq!(n_choose_k(5, 2), 10);
+    assert_eq!(n_choose_k(5, 6), 0);
+    assert_eq!(n_choose_k(52, 7), 133784560);
+    assert_eq!(DEALER_COMBINATIONS, 120);
+}
+
+#[test]
+fn average_result_calc() {
+    // Test average result calc
+    let test_player = [0, 1, 2, 3, 4, 5, 6]; // 7 card flush
+    let test_dealer = [12,1,2,25,14,15,16,26,27,39];
+    let avg = calculate_average_result(&test_player, &test_dealer);
+    assert_eq!(avg, 3.25);
+}
+
+#[test]
+#[ignore = "timing guard, run with cargo test --release -- --ignored"]
+fn throughput_within_limit() {
+    // Throughput regression guard: 100k seeded deals under a limit several times a release build's time
+    let elapsed = bench_throughput(100000);
+    assert!(elapsed <= std::time::Duration::from_secs(10), "100k deals took {:?}", elapsed);
+}
@@ -0,0 +1,46 @@
+//! Golden regression test: every named strategy's full result at a fixed seed, compared against the
+//! checked-in fixtures/golden.csv. Only goes through the public API.
+//!
+//! When a change to the results is intended, regenerate the file deliberately with
+//! HCF_UPDATE_GOLDEN=1 cargo test --test golden and review its diff
+
+use hcf::rules::GameConfig;
+use hcf::strategy::named_strategies;
+use hcf::simulation::simulate_strategy_with;
+
+const GOLDEN_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/golden.csv");
+// Small enough to replay in a debug build
+const GOLDEN_ITERATIONS: usize = 100;
+const GOLDEN_SEED: u64 = 179;
+
+/// One CSV row per named strategy with every SimulationResult field at GOLDEN_ITERATIONS and
+/// GOLDEN_SEED. Floats print with {:?} so they round-trip exactly
+fn golden_output() -> String {
+    let mut output = String::from("strategy,hands,folds,net,net_squares,units,play_multipliers,capped_raises,wins,losses,pushes,dealer_no_qualify,flush_bonus_bets,flush_bonus_net\n");
+    for (name, strategy) in named_strategies() {
+        let result = simulate_strategy_with(strategy, &GameConfig::STANDARD, GOLDEN_ITERATIONS, GOLDEN_SEED);
+        let outcomes = &result.outcomes;
+        output += &format!(
+            "{},{},{},{:?},{:?},{:?},{},{},{},{},{},{},{},{:?}\n",
+            name, result.hands, result.folds, result.net, result.net_squares, result.units, result.play_multipliers, result.capped_raises,
+            outcomes.wins, outcomes.losses, outcomes.pushes, outcomes.dealer_no_qualify, result.flush_bonus_bets, result.flush_bonus_net
+        );
+    }
+    output
+}
+
+#[test]
+fn golden_output_matches() {
+    // Full simulation output matches the checked-in golden file
+    let output = golden_output();
+    if std::env::var_os("HCF_UPDATE_GOLDEN").is_some() {
+        std::fs::write(GOLDEN_PATH, &output).unwrap_or_else(|e| panic!("could not write {}: {}", GOLDEN_PATH, e));
+        return;
+    }
+    let expected = std::fs::read_to_string(GOLDEN_PATH).unwrap_or_else(|e| panic!("could not read {}: {}", GOLDEN_PATH, e));
+    assert!(
+        output == expected,
+        "simulation output differs from {}, rerun with HCF_UPDATE_GOLDEN=1 cargo test --test golden if the change is intended",
+        GOLDEN_PATH
+    );
+}